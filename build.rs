@@ -0,0 +1,49 @@
+//! Generates the solution registry for the `registry` feature.
+//! Every scaffolded `src/bin/<year>-<day>.rs` is linked into the main binary as a module, see `template::registry`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out_path = Path::new(&out_dir).join("registry.rs");
+
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        fs::write(out_path, "").unwrap();
+        return;
+    }
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_stem()?.to_str()?.to_string();
+            let (year, day) = stem.split_once('-')?;
+            let is_day = path.extension()? == "rs"
+                && year.len() == 4
+                && day.len() == 2
+                && year.chars().chain(day.chars()).all(|c| c.is_ascii_digit());
+            is_day.then(|| (format!("{year}_{day}"), path.to_string_lossy().into_owned()))
+        })
+        .collect();
+
+    days.sort();
+
+    let mut registry = String::from("// @generated by build.rs\n\n");
+
+    for (day, path) in &days {
+        registry.push_str(&format!("#[path = {path:?}]\nmod day_{day};\n"));
+    }
+
+    registry
+        .push_str("\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (day, _) in &days {
+        registry.push_str(&format!("    day_{day}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n");
+
+    fs::write(out_path, registry).unwrap();
+}
@@ -3,9 +3,9 @@ use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 use std::collections::HashMap;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse = Input::from_str);
 
-struct Input {
+pub struct Input {
     pub(crate) left_column: Vec<u32>,
     pub(crate) right_column: Vec<u32>,
 }
@@ -39,8 +39,7 @@ impl Input {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let inp = Input::from_str(input);
+pub fn part_one(inp: &Input) -> Option<u32> {
     let total_diff = inp
         .left_column
         .par_iter()
@@ -51,9 +50,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(total_diff)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let inp = Input::from_str(input);
-
+pub fn part_two(inp: &Input) -> Option<u32> {
     let counts_in_rhs = inp
         .right_column
         .par_iter()
@@ -121,19 +118,19 @@ mod tests {
 
     #[test]
     fn test_day_1_valid_input() {
-        let result = part_one(VALID_INPUT_NO_MATCHES_ACROSS_COLUMNS);
+        let result = part_one(&Input::from_str(VALID_INPUT_NO_MATCHES_ACROSS_COLUMNS));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_day_2_valid_input_matches() {
-        let result = part_two(VALID_INPUT_MATCHES_ACROSS_COLUMNS);
+        let result = part_two(&Input::from_str(VALID_INPUT_MATCHES_ACROSS_COLUMNS));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_day_2_valid_input_no_matches() {
-        let result = part_two(VALID_INPUT_NO_MATCHES_ACROSS_COLUMNS);
+        let result = part_two(&Input::from_str(VALID_INPUT_NO_MATCHES_ACROSS_COLUMNS));
         assert_eq!(result, Some(0));
     }
 }
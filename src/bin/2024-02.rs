@@ -25,7 +25,7 @@ struct Input {
 
 impl Input {
     fn from_str(input: &str) -> Input {
-        let reports: Vec<Report> = input.lines().map(|line| Report::from_str(line)).collect();
+        let reports: Vec<Report> = input.lines().map(Report::from_str).collect();
         Input { reports }
     }
 }
@@ -57,7 +57,7 @@ impl Report {
 
         for (&prev, &current) in levels.iter().zip(levels.iter().skip(1)) {
             let diff = prev.abs_diff(current);
-            if diff < 1 || diff > 3 {
+            if !(1..=3).contains(&diff) {
                 return false;
             }
 
@@ -39,7 +39,7 @@ impl Input {
             for y in 0..cols {
                 for (x_direction, y_direction) in &DIRECTIONS {
                     let mut matched = true;
-                    for i in 0..chars.len() {
+                    for (i, ch) in chars.iter().enumerate() {
                         let nx = x as isize + i as isize * x_direction;
                         let ny = y as isize + i as isize * y_direction;
 
@@ -50,7 +50,7 @@ impl Input {
                         }
 
                         //didn't go out of bounds, but it's not a match
-                        if self.grid[nx as usize][ny as usize] != chars[i] {
+                        if self.grid[nx as usize][ny as usize] != *ch {
                             matched = false;
                             break;
                         }
@@ -213,7 +213,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(update.is_valid_for_rules(&rules), true);
+        assert!(update.is_valid_for_rules(&rules));
     }
 
     #[test]
@@ -244,7 +244,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(update.is_valid_for_rules(&rules), false);
+        assert!(!update.is_valid_for_rules(&rules));
     }
 
     #[test]
@@ -272,7 +272,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(update.reorder_with_rules(&rules), false);
+        assert!(!update.reorder_with_rules(&rules));
         assert_eq!(update.pages, seed_pages);
     }
 
@@ -308,7 +308,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(update.reorder_with_rules(&rules), true);
+        assert!(update.reorder_with_rules(&rules));
         assert_eq!(update.pages, vec![2, 5, 3, 1, 4]);
     }
 }
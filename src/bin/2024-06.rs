@@ -40,10 +40,10 @@ pub fn part_two(input: &str) -> Option<u32> {
             let mut new_map = input.map.clone();
             new_map[*y_pos][*x_pos] = MapSlot::Obstacle;
 
-            match simulate_guard_movement(&new_map, input.guard_initial_position) {
-                MovementSimulationResult::Loop => true,
-                _ => false
-            }
+            matches!(
+                simulate_guard_movement(&new_map, input.guard_initial_position),
+                MovementSimulationResult::Loop
+            )
         })
         .count();
 
@@ -94,7 +94,7 @@ impl Input {
                     .map(|(xpos, char)| {
                         let map_slot = parse_char_into_mapslot(char);
                         if let MapSlot::Guard(_) = &map_slot {
-                            if let Some(_) = guard_pos.replace((ypos, xpos)) {
+                            if guard_pos.replace((ypos, xpos)).is_some() {
                                 panic!("Multiple guards")
                             }
                         }
@@ -120,11 +120,11 @@ enum MovementSimulationResult {
 }
 
 //returns all the positions the guard would visit
-fn simulate_guard_movement(map: &Vec<Vec<MapSlot>>, guard_initial_position: (usize, usize)) -> MovementSimulationResult {
+fn simulate_guard_movement(map: &[Vec<MapSlot>], guard_initial_position: (usize, usize)) -> MovementSimulationResult {
     // if against obstacle in orientation, return existing position and new orientation
     // if we would go out of bounds, return None
     // otherwise return new position with existing orientation
-    fn move_guard_or_complete(map: &Vec<Vec<MapSlot>>, orientation: Orientation, position: (usize, usize)) -> Option<(Orientation, (usize, usize))> {
+    fn move_guard_or_complete(map: &[Vec<MapSlot>], orientation: Orientation, position: (usize, usize)) -> Option<(Orientation, (usize, usize))> {
         match orientation {
             Orientation::Up => {
                 //check if above is out of bounds
@@ -223,8 +223,6 @@ fn simulate_guard_movement(map: &Vec<Vec<MapSlot>>, guard_initial_position: (usi
 
 #[cfg(test)]
 mod tests {
-    use std::f32::consts::E;
-    use nom::Or;
     use super::*;
 
     use MapSlot::*;
@@ -305,6 +303,12 @@ mod tests {
         Input::from_str("");
     }
 
+    #[test]
+    #[should_panic]
+    fn test_parse_input_no_guard() {
+        Input::from_str(SMALL_VALID_WITH_NO_GUARD);
+    }
+
     #[test]
     #[should_panic]
     fn test_parse_input_multiple_guards() {
@@ -347,6 +351,6 @@ mod tests {
 
         let expected = MovementSimulationResult::Loop;
         let actual = simulate_guard_movement(&map, (1, 3));
-        assert_eq!(actual, MovementSimulationResult::Loop);
+        assert_eq!(actual, expected);
     }
 }
@@ -42,14 +42,12 @@ struct Calibration {
 impl Calibration {
     fn from_str(input: &str) -> Self {
         let (_, (total_str, nums_as_strs)) = separated_pair(digit1::<&str, nom::error::Error<&str>>, tag(": "), separated_list0(space1, digit1))(input)
-            .expect(&format!("Invalid calibration syntax: {}", input));
+            .unwrap_or_else(|_| panic!("Invalid calibration syntax: {}", input));
 
-        let total: usize = total_str.parse().expect(
-            &format!("total number failed to parse to u32: {}, on line content: {}", total_str, input)
-        );
+        let total: usize = total_str.parse().unwrap_or_else(|_| panic!("total number failed to parse to u32: {}, on line content: {}", total_str, input));
         let nums: Vec<usize> = nums_as_strs.iter()
             .map(|num| num.parse()
-                .expect(&format!("number failed to parse to u32: {}, on line content: {}", num, input)))
+                .unwrap_or_else(|_| panic!("number failed to parse to u32: {}, on line content: {}", num, input)))
             .collect();
 
         Calibration {
@@ -62,7 +60,7 @@ impl Calibration {
 impl Input {
     fn from_str(input: &str) -> Self {
         let calibrations = input.lines()
-            .map(|line| Calibration::from_str(line))
+            .map(Calibration::from_str)
             .collect::<Vec<_>>();
         Input { calibrations }
     }
@@ -86,8 +84,8 @@ fn can_operators_solve(calibration: &Calibration, concat_operator_enabled: bool)
 
     let num_operation_slots = last_nums_index;
 
-    let ops_orders = std::iter::repeat(&operations)
-        .take(num_operation_slots)
+    let ops_orders = (0..num_operation_slots)
+        .map(|_| &operations)
         .multi_cartesian_product();
 
     for op_order in ops_orders {
@@ -163,8 +161,8 @@ mod tests {
             total: 25,
             nums: vec![5, 19, 1],
         };
-        let actual = can_operators_solve(&calibration);
-        assert_eq!(true, actual);
+        let actual = can_operators_solve(&calibration, false);
+        assert!(actual);
     }
 
     #[test]
@@ -173,8 +171,8 @@ mod tests {
             total: 190,
             nums: vec![10, 19],
         };
-        let actual = can_operators_solve(&calibration);
-        assert_eq!(true, actual);
+        let actual = can_operators_solve(&calibration, false);
+        assert!(actual);
     }
 
     #[test]
@@ -183,8 +181,8 @@ mod tests {
             total: 200,
             nums: vec![8, 2, 20],
         };
-        let actual = can_operators_solve(&calibration);
-        assert_eq!(true, actual);
+        let actual = can_operators_solve(&calibration, false);
+        assert!(actual);
     }
 
     #[test]
@@ -193,7 +191,7 @@ mod tests {
             total: 2521,
             nums: vec![5, 2, 1, 1, 5],
         };
-        let actual = can_operators_solve(&calibration);
-        assert_eq!(false, actual);
+        let actual = can_operators_solve(&calibration, false);
+        assert!(!actual);
     }
 }
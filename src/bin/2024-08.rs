@@ -206,7 +206,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_parse_empty_input() {
-        let actual = Input::from_str("");
+        Input::from_str("");
     }
 
     #[test]
@@ -63,7 +63,7 @@ impl Input {
     }
 }
 
-fn calculate_checksum(blocks: &Vec<DiskBlock>) -> u64 {
+fn calculate_checksum(blocks: &[DiskBlock]) -> u64 {
     blocks
         .iter()
         .enumerate()
@@ -76,8 +76,8 @@ fn calculate_checksum(blocks: &Vec<DiskBlock>) -> u64 {
         .sum()
 }
 
-fn defrag_bitwise(blocks: &Vec<DiskBlock>) -> Vec<DiskBlock> {
-    let mut defragged = blocks.clone();
+fn defrag_bitwise(blocks: &[DiskBlock]) -> Vec<DiskBlock> {
+    let mut defragged = blocks.to_vec();
 
     // Find the first empty position
     let mut empty_pos = 0;
@@ -107,8 +107,8 @@ fn defrag_bitwise(blocks: &Vec<DiskBlock>) -> Vec<DiskBlock> {
     defragged
 }
 
-fn defrag_filewise(blocks: &Vec<DiskBlock>, repr: &Vec<u8>) -> Vec<DiskBlock> {
-    let mut defragged = blocks.clone();
+fn defrag_filewise(blocks: &[DiskBlock], repr: &[u8]) -> Vec<DiskBlock> {
+    let mut defragged = blocks.to_vec();
 
     //find the next backwards file
     for initial_position in (0..repr.len()).rev().step_by(2) {
@@ -178,11 +178,10 @@ mod tests {
 
     const SIMPLE_INPUT: &str = "202056";
 
-    /*
-    tests were helpful then changed input format, will maybe fix later
     #[test]
     fn test_parse_simple_input() {
         let expected = Input {
+            repr: vec![2, 0, 2, 0, 5, 6],
             blocks: vec![File(0), File(0), File(1), File(1),
                          File(2), File(2), File(2), File(2),
                          File(2), Empty, Empty, Empty,
@@ -198,12 +197,11 @@ mod tests {
                           File(2), File(2), File(2), File(2),
                           File(2), Empty, Empty, Empty,
                           Empty, Empty, Empty];
-        let actual = defrag_bitwise(&blocks);
+        let defragged = defrag_bitwise(&blocks);
+        let actual = (calculate_checksum(&defragged), defragged);
 
         let expected_checksum = 65;
         let expected_blocks = vec![File(0), File(0), File(1), File(1), File(2), File(2), File(2), File(2), File(2), Empty, Empty, Empty, Empty, Empty, Empty];
-        assert_eq!(actual, (expected_blocks, expected_checksum));
+        assert_eq!(actual, (expected_checksum, expected_blocks));
     }
-
-     */
 }
@@ -37,7 +37,7 @@ impl Input {
             .map(|line| {
                 line
                     .chars()
-                    .map(|ch| (ch.to_digit(10).unwrap() as u8))
+                    .map(|ch| ch.to_digit(10).unwrap() as u8)
                     .collect()
             })
             .collect();
@@ -105,7 +105,7 @@ fn calculate_rating_for_trailhead(
     count_trails(map, tp.y, tp.x, memo)
 }
 
-fn get_all_trailhead_positions(map: &Vec<Vec<u8>>) -> Vec<TrailheadPosition> {
+fn get_all_trailhead_positions(map: &[Vec<u8>]) -> Vec<TrailheadPosition> {
     let mut positions = Vec::with_capacity(850);
     for (y, row) in map.iter().enumerate() {
         for (x, num) in row.iter().enumerate() {
@@ -117,8 +117,8 @@ fn get_all_trailhead_positions(map: &Vec<Vec<u8>>) -> Vec<TrailheadPosition> {
     positions
 }
 
-fn calculate_score_for_trailhead_position(map: &Vec<Vec<u8>>, tp: &TrailheadPosition) -> usize {
-    fn traversable_neighbors_of_position(map: &Vec<Vec<u8>>, y: usize, x: usize) -> Vec<(usize, usize)> {
+fn calculate_score_for_trailhead_position(map: &[Vec<u8>], tp: &TrailheadPosition) -> usize {
+    fn traversable_neighbors_of_position(map: &[Vec<u8>], y: usize, x: usize) -> Vec<(usize, usize)> {
         let mut result = Vec::with_capacity(4);
 
         for &(dx, dy) in &DIRECTIONS {
@@ -167,4 +167,12 @@ fn calculate_score_for_trailhead_position(map: &Vec<Vec<u8>>, tp: &TrailheadPosi
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const SIMPLE_MAP: &str = "89010123\n78121874\n87430965\n96549874\n45678903\n32019012\n01329801\n10456732\n";
+
+    #[test]
+    fn test_simple_map() {
+        assert_eq!(part_one(SIMPLE_MAP), Some(36));
+        assert_eq!(part_two(SIMPLE_MAP), Some(81));
+    }
 }
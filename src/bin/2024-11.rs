@@ -134,4 +134,13 @@ fn blink(stones: Vec<Stone>) -> Vec<Stone> {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const SIMPLE_INPUT: &str = "125 17\n";
+
+    #[test]
+    fn test_blink_simple_input() {
+        let input = Input::from_str(SIMPLE_INPUT);
+        assert_eq!(blink_large_times_and_get_stone_count(input.stones, 6), 22);
+        assert_eq!(part_one(SIMPLE_INPUT), Some(55312));
+    }
 }
@@ -0,0 +1,460 @@
+/// Points, vectors and directions on a two-dimensional plane.
+/// Coordinates follow the convention of puzzle maps: `x` grows to the right and `y` grows downwards, so
+/// [`Direction::Up`] points to negative `y`.
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Position;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Difference between two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point that lies outside the bounds of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfBounds {
+    pub point: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "point {} is out of bounds of a {}x{} grid",
+            self.point, self.width, self.height
+        )
+    }
+}
+
+impl Error for OutOfBounds {}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u64 {
+        (other - self).manhattan_length()
+    }
+
+    /// Distance if diagonal steps are allowed, i.e. the number of king moves between two points.
+    pub fn chebyshev_distance(self, other: Self) -> u64 {
+        (other - self).chebyshev_length()
+    }
+
+    /// The point one step away in a direction.
+    #[must_use]
+    pub fn step(self, direction: impl Into<Vector>) -> Self {
+        self + direction.into()
+    }
+
+    /// The orthogonal neighbours, clockwise starting at the top.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The orthogonal and diagonal neighbours, clockwise starting at the top.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Direction8::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// Position of the point in a grid of the given dimensions.
+    pub fn to_position(self, width: usize, height: usize) -> Result<Position, OutOfBounds> {
+        let out_of_bounds = OutOfBounds {
+            point: self,
+            width,
+            height,
+        };
+
+        let x = usize::try_from(self.x).map_err(|_| out_of_bounds)?;
+        let y = usize::try_from(self.y).map_err(|_| out_of_bounds)?;
+        if x < width && y < height {
+            Ok((x, y))
+        } else {
+            Err(out_of_bounds)
+        }
+    }
+
+    /// Index of the point in the row-major cells of a grid of the given dimensions.
+    pub fn to_index(self, width: usize, height: usize) -> Result<usize, OutOfBounds> {
+        self.to_position(width, height).map(|(x, y)| y * width + x)
+    }
+
+    /// The point at an index of the row-major cells of a grid with the given width.
+    /// Returns [`None`] for a width of zero, as such a grid has no cells.
+    pub fn from_index(index: usize, width: usize) -> Option<Self> {
+        (width > 0).then(|| Self::from((index % width, index / width)))
+    }
+}
+
+impl From<Position> for Point {
+    /// Grid positions always fit, as no grid can hold more than `i64::MAX` cells.
+    fn from((x, y): Position) -> Self {
+        Self::new(x as i64, y as i64)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Vector {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_length(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev_length(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    /// Rotate by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Rotate by 90 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        self + -rhs
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Vector {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self as usize + steps) % Self::ALL.len()]
+    }
+
+    /// Turn by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+
+    /// Turn by 90 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// The direction of an arrow as drawn in puzzle maps: `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+        }
+    }
+}
+
+/// One of the four orthogonal and four diagonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self as usize + steps) % Self::ALL.len()]
+    }
+
+    /// Turn by 45 degrees counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    /// Turn by 45 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        match direction {
+            Direction8::Up => Vector::new(0, -1),
+            Direction8::UpRight => Vector::new(1, -1),
+            Direction8::Right => Vector::new(1, 0),
+            Direction8::DownRight => Vector::new(1, 1),
+            Direction8::Down => Vector::new(0, 1),
+            Direction8::DownLeft => Vector::new(-1, 1),
+            Direction8::Left => Vector::new(-1, 0),
+            Direction8::UpLeft => Vector::new(-1, -1),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Direction8, OutOfBounds, Point, Vector};
+
+    #[test]
+    fn adds_and_subtracts() {
+        let mut point = Point::new(2, 3);
+        assert_eq!(point + Vector::new(1, -4), Point::new(3, -1));
+        assert_eq!(point - Vector::new(1, -4), Point::new(1, 7));
+        assert_eq!(Point::new(5, 1) - point, Vector::new(3, -2));
+
+        point += Vector::new(1, 1) * 3;
+        assert_eq!(point, Point::new(5, 6));
+        point -= -Vector::new(1, 0);
+        assert_eq!(point, Point::new(6, 6));
+
+        assert_eq!(Vector::new(1, 2) + Vector::new(3, 4), Vector::new(4, 6));
+        assert_eq!(Vector::new(1, 2) - Vector::new(3, 4), Vector::new(-2, -2));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a.manhattan_distance(b), 10);
+        assert_eq!(a.chebyshev_distance(b), 6);
+        assert_eq!(b.manhattan_distance(a), 10);
+        assert_eq!(Point::ORIGIN.manhattan_distance(Point::ORIGIN), 0);
+        assert_eq!(Vector::new(i64::MIN, 0).manhattan_length(), 1 << 63);
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.reverse(), Direction::Up);
+
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+        assert!(Direction8::DownLeft.is_diagonal());
+        assert!(!Direction8::Left.is_diagonal());
+
+        for direction in Direction::ALL {
+            let vector = Vector::from(direction);
+            assert_eq!(Vector::from(direction.turn_right()), vector.turn_right());
+            assert_eq!(Vector::from(direction.turn_left()), vector.turn_left());
+            assert_eq!(Vector::from(direction.reverse()), -vector);
+            assert_eq!(Vector::from(Direction8::from(direction)), vector);
+        }
+
+        assert_eq!(Direction::from_arrow('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_arrow('.'), None);
+    }
+
+    #[test]
+    fn steps_to_neighbours() {
+        let point = Point::new(0, 0);
+        assert_eq!(point.step(Direction::Up), Point::new(0, -1));
+        assert_eq!(point.step(Direction8::DownLeft), Point::new(-1, 1));
+
+        let neighbours: Vec<_> = point.neighbours4().collect();
+        assert_eq!(
+            neighbours,
+            vec![
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(point.neighbours8().count(), 8);
+        assert!(point
+            .neighbours8()
+            .all(|n| n.chebyshev_distance(point) == 1));
+    }
+
+    #[test]
+    fn converts_grid_indices() {
+        assert_eq!(Point::new(2, 1).to_position(3, 2), Ok((2, 1)));
+        assert_eq!(Point::new(2, 1).to_index(3, 2), Ok(5));
+        assert_eq!(Point::from_index(5, 3), Some(Point::new(2, 1)));
+        assert_eq!(Point::from_index(0, 0), None);
+        assert_eq!(Point::from((2, 1)), Point::new(2, 1));
+
+        for point in [Point::new(-1, 0), Point::new(3, 0), Point::new(0, 2)] {
+            assert_eq!(
+                point.to_index(3, 2),
+                Err(OutOfBounds {
+                    point,
+                    width: 3,
+                    height: 2
+                })
+            );
+        }
+
+        let err = Point::new(0, -1).to_position(3, 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "point (0, -1) is out of bounds of a 3x2 grid"
+        );
+    }
+}
@@ -0,0 +1,541 @@
+/// A two-dimensional grid with flat storage, as found in many puzzle inputs.
+/// Cells are addressed by `(x, y)` positions, where `x` is the column and `y` the row, starting at the top left.
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{OutOfBounds, Point};
+
+/// Position of a cell as `(x, y)`.
+pub type Position = (usize, usize);
+
+/// Offsets `(dx, dy)` of the orthogonal neighbours, clockwise starting at the top.
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets `(dx, dy)` of the orthogonal and diagonal neighbours, clockwise starting at the top.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A row is not as wide as the first one.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell mapper rejected a character.
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected a row of {expected} cells, found {found}"
+            ),
+            ParseGridError::InvalidCell { line, column, cell } => {
+                write!(f, "line {line}, column {column}: invalid cell `{cell}`")
+            }
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid with one row per line, mapping every character to a cell.
+    /// Trailing newlines are ignored, rows of different widths are rejected.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    /// Like [`Grid::parse`], for cell mappers that reject characters by returning [`None`].
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or(ParseGridError::InvalidCell {
+                    line: y + 1,
+                    column: x + 1,
+                    cell: c,
+                })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - row_start;
+            if y == 0 {
+                width = found;
+            } else if found != width {
+                return Err(ParseGridError::RaggedRow {
+                    line: y + 1,
+                    expected: width,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Build a grid from its cells in row-major order.
+    /// Returns [`None`] if the number of cells does not match the dimensions.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (width.checked_mul(height)? == cells.len()).then_some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.1 * self.width + position.0)
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Position of a point in the grid, or the reason why it has none.
+    pub fn position(&self, point: Point) -> Result<Position, OutOfBounds> {
+        point.to_position(self.width, self.height)
+    }
+
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        self.position(point)
+            .ok()
+            .and_then(|position| self.get(position))
+    }
+
+    pub fn get_point_mut(&mut self, point: Point) -> Option<&mut T> {
+        let position = self.position(point).ok()?;
+        self.get_mut(position)
+    }
+
+    /// Move from a position by an offset, returning [`None`] if that leaves the grid.
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// All cells with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        position: Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (Position, &'a T)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| self.offset(position, offset))
+            .map(|position| (position, &self[position]))
+    }
+
+    /// Orthogonal neighbours of a position that lie inside the grid, clockwise starting at the top.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position, &OFFSETS_4)
+    }
+
+    /// Orthogonal and diagonal neighbours of a position that lie inside the grid, clockwise starting at the top.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position, &OFFSETS_8)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // not `chunks`, which panics for grids of width zero.
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Cells of a column, top to bottom. Empty if the column is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals running from the top left to the bottom right, starting with the one at the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.cell_dimensions();
+        let left = (0..height).rev().map(|y| (0, y));
+        let top = (1..width).map(|x| (x, 0));
+        left.chain(top).map(|start| self.walk(start, (1, 1)))
+    }
+
+    /// Diagonals running from the top right to the bottom left, starting with the one at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.cell_dimensions();
+        let top = (0..width).map(|x| (x, 0));
+        let right = (1..height).map(move |y| (width - 1, y));
+        top.chain(right).map(|start| self.walk(start, (-1, 1)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Dimensions of the grid, or zero for both if it has no cells.
+    fn cell_dimensions(&self) -> (usize, usize) {
+        if self.is_empty() {
+            (0, 0)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Cells from a start position in a direction, until the edge of the grid.
+    pub fn walk(&self, start: Position, step: (isize, isize)) -> impl Iterator<Item = &T> {
+        std::iter::successors(self.contains(start).then_some(start), move |&position| {
+            self.offset(position, step)
+        })
+        .map(|position| &self[position])
+    }
+
+    /// Position of the first cell in row-major order that matches the predicate.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.position_of(i))
+    }
+
+    /// Positions of all cells that match the predicate, in row-major order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter_map(move |(position, cell)| predicate(cell).then_some(position))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Build a grid by looking up the cell at every position of the resulting grid.
+    fn rearrange(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(Position) -> Position,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirror the grid along its main diagonal, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.rearrange(self.height, self.width, |(x, y)| (y, x))
+    }
+
+    /// Rotate the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        self.rearrange(self.height, self.width, |(x, y)| (y, height - 1 - x))
+    }
+
+    /// Rotate the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        self.rearrange(self.height, self.width, |(x, y)| (width - 1 - y, x))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        let index = self.index_of(position).unwrap_or_else(|| {
+            panic!(
+                "position {position:?} is out of bounds of a {}x{} grid",
+                self.width, self.height
+            )
+        });
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let index = self.index_of(position).unwrap_or_else(|| {
+            panic!(
+                "position {position:?} is out of bounds of a {}x{} grid",
+                self.width, self.height
+            )
+        });
+        &mut self.cells[index]
+    }
+}
+
+/// Render the grid as text, one line per row, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::geometry::{Direction, Point};
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(INPUT, |c| c).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.cells(), &['a', 'b', 'c', 'd', 'e', 'f']);
+
+        assert_eq!(Grid::parse("abc\r\ndef", |c| c), Ok(grid.clone()));
+        assert_eq!(Grid::parse("abc\ndef\n\n", |c| c), Ok(grid));
+
+        let digits = Grid::try_parse("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits[(1, 1)], 4);
+
+        let empty = Grid::parse("", |c| c).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+
+        for (width, height) in [(0, 2), (2, 0)] {
+            let empty: Grid<char> = Grid::from_cells(width, height, vec![]).unwrap();
+            assert!(empty.is_empty());
+            assert_eq!(empty.diagonals().count(), 0);
+            assert_eq!(empty.anti_diagonals().count(), 0);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!(
+            Grid::parse("abc\nde\n", |c| c),
+            Err(ParseGridError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+
+        let err = Grid::try_parse("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: invalid cell `x`");
+    }
+
+    #[test]
+    fn indexes_with_bounds_checks() {
+        let mut grid = grid();
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+
+        grid[(0, 1)] = 'x';
+        *grid.get_mut((1, 0)).unwrap() = 'y';
+        assert_eq!(grid.to_string(), "ayc\nxef");
+
+        assert_eq!(grid.offset((0, 0), (1, 1)), Some((1, 1)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((2, 1), (1, 0)), None);
+    }
+
+    #[test]
+    fn indexes_with_points() {
+        let mut grid = grid();
+        let point = Point::new(1, 0).step(Direction::Down);
+
+        assert_eq!(grid.position(point), Ok((1, 1)));
+        assert_eq!(grid.get_point(point), Some(&'e'));
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+        assert!(grid.position(Point::new(0, 2)).is_err());
+
+        *grid.get_point_mut(point).unwrap() = 'x';
+        assert_eq!(grid.to_string(), "abc\ndxf");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds of a 3x2 grid")]
+    fn panics_on_out_of_bounds_index() {
+        let _ = grid()[(3, 0)];
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = grid();
+
+        let corner: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![((1, 0), &'b'), ((0, 1), &'d')]);
+
+        let center: String = grid.neighbours4((1, 1)).map(|(_, c)| c).collect();
+        assert_eq!(center, "bfd");
+
+        let all: String = grid.neighbours8((1, 0)).map(|(_, c)| c).collect();
+        assert_eq!(all, "cfeda");
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(collect(grid.columns()), vec!["ad", "be", "cf"]);
+
+        assert_eq!(collect(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+        assert_eq!(grid.walk((0, 1), (1, -1)).collect::<String>(), "db");
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = grid();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_left().rotate_right(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = Grid::parse("#.#\n.^#", |c| c).unwrap();
+
+        assert_eq!(grid.find(|&c| c == '^'), Some((1, 1)));
+        assert_eq!(grid.find(|&c| c == 'x'), None);
+        assert_eq!(
+            grid.positions(|&c| c == '#').collect::<Vec<_>>(),
+            vec![(0, 0), (2, 0), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn maps_and_renders() {
+        let grid = Grid::parse("#.\n.#", |c| c == '#').unwrap();
+        let rendered = grid.map(|&wall| if wall { '#' } else { '.' });
+        assert_eq!(rendered.to_string(), "#.\n.#");
+        assert_eq!(
+            Grid::from_cells(2, 1, vec![1, 2]).unwrap().to_string(),
+            "12"
+        );
+        assert_eq!(Grid::from_cells(2, 2, vec![1, 2]), None);
+    }
+}
@@ -1,3 +1,6 @@
+pub mod geometry;
+pub mod grid;
+pub mod parse;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
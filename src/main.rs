@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, time, verify};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,8 +7,13 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        benchmark_chart::Scale,
+        compare::{parse_tolerance, CompareConfig},
+        runner::{parse_duration, BenchConfig},
+        Day, Year,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -21,40 +26,124 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
+            split_examples: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            accept: Option<u8>,
+            bench: Option<BenchConfig>,
+            input: Option<String>,
+            example: bool,
+        },
+        SolveCorpus {
+            day: Day,
+            release: bool,
+        },
+        SolveWatch {
+            day: Day,
+            release: bool,
+            input: Option<String>,
+            example: bool,
+            test: bool,
         },
         All {
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+        },
+        Verify {
+            release: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            chart_scale: Scale,
+            bench: BenchConfig,
+        },
+        TimeHistory {
+            day: Day,
+        },
+        TimeCompare {
+            day: Option<Day>,
+            bench: BenchConfig,
+            compare: CompareConfig,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    fn parse_bench(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let budget = args.opt_value_from_fn("--budget", parse_duration)?;
+        let min_samples = args.opt_value_from_str("--min-samples")?;
+        let max_samples = args.opt_value_from_str("--max-samples")?;
+
+        Ok(BenchConfig::from_env()?.with_overrides(budget, min_samples, max_samples)?)
+    }
+
+    fn parse_jobs(s: &str) -> Result<usize, String> {
+        match s.parse::<usize>() {
+            Ok(jobs) if jobs > 0 => Ok(jobs),
+            _ => Err(format!(
+                "invalid number of jobs `{s}`, expecting at least 1."
+            )),
+        }
+    }
+
+    /// Parse the command and the year it applies to, either passed as `--year` or read from `AOC_YEAR`.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args
+                    .opt_value_from_fn(["-j", "--jobs"], parse_jobs)?
+                    .unwrap_or(1),
+                timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
             },
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+            },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                day: args.free_from_str()?,
+            },
+            Some("time") if args.contains("--compare") => {
+                let bench = parse_bench(&mut args)?;
+                let tolerance = args.opt_value_from_fn("--tolerance", parse_tolerance)?;
+                let significance = args.opt_value_from_str("--significance")?;
+
+                AppArguments::TimeCompare {
+                    day: args.opt_free_from_str()?,
+                    bench,
+                    compare: CompareConfig::default().with_overrides(tolerance, significance)?,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let chart_scale = if args.contains("--log-scale") {
+                    Scale::Log
+                } else {
+                    Scale::Linear
+                };
+                let bench = parse_bench(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    chart_scale,
+                    bench,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -67,13 +156,37 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
+                split_examples: args.contains("--split-examples"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") if args.contains("--corpus") => AppArguments::SolveCorpus {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
+            Some("solve") if args.contains("--watch") => AppArguments::SolveWatch {
+                day: args.free_from_str()?,
+                release: args.contains("--release"),
+                input: args.opt_value_from_str("--input")?,
+                example: args.contains("--example"),
+                test: args.contains("--test"),
+            },
+            Some("solve") => {
+                let time = args.contains("--time");
+                let bench = parse_bench(&mut args)?;
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    force: args.contains("--force"),
+                    accept: args.opt_value_from_str("--accept")?,
+                    dhat: args.contains("--dhat"),
+                    bench: time.then_some(bench),
+                    input: args.opt_value_from_str("--input")?,
+                    example: args.contains("--example"),
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -91,54 +204,114 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        let year = year.or_else(Year::from_env).ok_or(
+            "no year set. Pass `--year <year>` or set `AOC_YEAR` in `.cargo/config.toml`.",
+        )?;
+
+        Ok((year, app_args))
     }
 }
 
+/// Solutions linked into this binary, see `template::registry`.
+#[cfg(feature = "registry")]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
 fn main() {
+    #[cfg(feature = "registry")]
+    advent_of_code::template::registry::register(registry::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+        Ok((year, args)) => {
+            advent_of_code::template::warn_legacy_data(year);
+
+            match args {
+                AppArguments::SolveCorpus { day, release } => solve::corpus(year, day, release),
+                AppArguments::SolveWatch {
+                    day,
+                    release,
+                    input,
+                    example,
+                    test,
+                } => solve::watch(year, day, release, input, example, test),
+                AppArguments::All {
+                    release,
+                    jobs,
+                    timeout,
+                } => all::handle(year, release, jobs, timeout),
+                AppArguments::Verify { release } => verify::handle(year, release),
+                AppArguments::Time {
+                    day,
+                    all,
+                    store,
+                    chart_scale,
+                    bench,
+                } => time::handle(year, day, all, store, chart_scale, bench),
+                AppArguments::TimeHistory { day } => time::show_history(year, day),
+                AppArguments::TimeCompare {
+                    day,
+                    bench,
+                    compare,
+                } => time::compare(year, day, bench, compare),
+                AppArguments::Download { day } => download::handle(year, day),
+                AppArguments::Read { day } => read::handle(year, day),
+                AppArguments::Scaffold {
+                    day,
+                    download,
+                    overwrite,
+                    template,
+                    answer_type,
+                    split_examples,
+                } => {
+                    // download first, so that the template can use the title of the puzzle.
+                    if download {
+                        download::handle(year, day);
                     }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
+                    scaffold::handle(
+                        year,
+                        day,
+                        overwrite,
+                        template.as_deref(),
+                        answer_type,
+                        split_examples,
+                    );
+                }
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    force,
+                    accept,
+                    bench,
+                    input,
+                    example,
+                } => solve::handle(
+                    year, day, release, dhat, submit, force, accept, bench, input, example,
+                ),
+                #[cfg(feature = "today")]
+                AppArguments::Today => {
+                    match Day::today() {
+                        Some(day) => {
+                            download::handle(year, day);
+                            scaffold::handle(year, day, false, None, None, false);
+                            read::handle(year, day)
+                        }
+                        None => {
+                            eprintln!(
+                                "`today` command can only be run between the 1st and \
                             the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                            );
+                            process::exit(1)
+                        }
+                    };
+                }
             }
-        },
+        }
     };
 }
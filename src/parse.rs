@@ -0,0 +1,422 @@
+/// Parsers for the common shapes of puzzle inputs, built on `nom`.
+/// Combinators return an [`IResult`] and can be mixed with the parsers of `nom` itself. Run the parser of a whole
+/// input with [`all`], which ignores trailing newlines and reports failures with their line and column.
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::character::complete::{char, digit1, line_ending, none_of, one_of, space0, space1};
+use nom::combinator::{all_consuming, map_opt, map_res, opt, recognize};
+use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{pair, separated_pair, tuple};
+use nom::Parser;
+
+use crate::grid::Grid;
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, Error<'a>>;
+
+/// Error of a parser, holding the remaining input at which it failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub message: Cow<'static, str>,
+}
+
+impl<'a> Error<'a> {
+    pub fn new(input: &'a str, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            input,
+            message: message.into(),
+        }
+    }
+}
+
+impl<'a> ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self::new(input, describe(kind))
+    }
+
+    /// Keep the innermost error, which points to where parsing actually failed.
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(_: &'a str, context: &'static str, other: Self) -> Self {
+        Self::new(other.input, format!("{context}: {}", other.message))
+    }
+}
+
+impl<'a, E: Display> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, _: ErrorKind, e: E) -> Self {
+        Self::new(input, e.to_string())
+    }
+}
+
+fn describe(kind: ErrorKind) -> Cow<'static, str> {
+    match kind {
+        ErrorKind::Eof => "unexpected trailing input".into(),
+        ErrorKind::Digit => "expected a number".into(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "expected whitespace".into(),
+        ErrorKind::CrLf => "expected a line break".into(),
+        ErrorKind::MapOpt | ErrorKind::NoneOf => "unexpected character".into(),
+        ErrorKind::Char | ErrorKind::Tag => "unexpected input".into(),
+        kind => kind.description().to_lowercase().into(),
+    }
+}
+
+/// Failure of [`all`], positioned in the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    /// The rest of the line at which parsing failed.
+    pub found: String,
+}
+
+impl InputError {
+    const MAX_FOUND_CHARS: usize = 20;
+
+    fn new(input: &str, error: Error) -> Self {
+        let offset = input.len() - error.input.len();
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            message: error.message.into_owned(),
+            found: error.input.lines().next().unwrap_or_default().into(),
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+
+        if self.found.is_empty() {
+            write!(f, ", found end of line")
+        } else if self.found.chars().count() > Self::MAX_FOUND_CHARS {
+            let found: String = self.found.chars().take(Self::MAX_FOUND_CHARS).collect();
+            write!(f, ", found `{found}…`")
+        } else {
+            write!(f, ", found `{}`", self.found)
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Parse a whole input. Trailing newlines are ignored, any other input left over is an error.
+pub fn all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, Error<'a>>,
+) -> Result<O, InputError> {
+    let trimmed = input.trim_end_matches(['\n', '\r']);
+
+    match all_consuming(parser)(trimmed) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(InputError::new(trimmed, e)),
+        Err(nom::Err::Incomplete(_)) => Err(InputError::new(
+            trimmed,
+            Error::new(&trimmed[trimmed.len()..], "incomplete input"),
+        )),
+    }
+}
+
+/// A number without sign, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<'_, T>
+where
+    T::Err: Display,
+{
+    map_res(digit1, str::parse)(input)
+}
+
+/// A number with an optional sign, e.g. `-42` or `+42`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<'_, T>
+where
+    T::Err: Display,
+{
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Separator of list items: a comma or spaces, but never a line break.
+fn list_separator(input: &str) -> IResult<'_, &str> {
+    alt((recognize(tuple((space0, char(','), space0))), space1))(input)
+}
+
+/// Numbers without sign on a single line, separated by spaces or commas, e.g. `3   4` or `1,2, 3`.
+pub fn unsigned_list<T: FromStr>(input: &str) -> IResult<'_, Vec<T>>
+where
+    T::Err: Display,
+{
+    separated_list1(list_separator, unsigned)(input)
+}
+
+/// Numbers with an optional sign on a single line, separated by spaces or commas, e.g. `7 -2 +3`.
+pub fn signed_list<T: FromStr>(input: &str) -> IResult<'_, Vec<T>>
+where
+    T::Err: Display,
+{
+    separated_list1(list_separator, signed)(input)
+}
+
+/// Turn a recoverable error into a failure, so that enclosing parsers report it instead of trying alternatives.
+fn cut_error(e: nom::Err<Error>) -> nom::Err<Error> {
+    match e {
+        nom::Err::Error(e) => nom::Err::Failure(e),
+        e => e,
+    }
+}
+
+/// Whether another line with content follows a line ending, i.e. the input is neither over nor at a blank line.
+fn has_next_line(input: &str) -> bool {
+    !input.is_empty() && !input.starts_with(['\n', '\r'])
+}
+
+/// One record per line, each of which must be consumed entirely. Ends before a blank line.
+/// A record that fails to parse fails the whole list with its own error, rather than ending the list before it.
+pub fn lines<'a, O>(
+    mut record: impl Parser<&'a str, O, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |input| {
+        let mut records = vec![];
+        let mut rest = input;
+
+        loop {
+            let (line, next) = rest.split_at(rest.find(['\n', '\r']).unwrap_or(rest.len()));
+
+            let result = match record.parse(line) {
+                Ok(("", output)) => Ok(output),
+                Ok((remaining, _)) => Err(nom::Err::Error(Error::new(
+                    remaining.trim_start_matches([' ', '\t']),
+                    "unexpected trailing input",
+                ))),
+                Err(e) => Err(e),
+            };
+            // the first record may still fail softly, e.g. for alternatives of `lines`.
+            let output = if records.is_empty() {
+                result?
+            } else {
+                result.map_err(cut_error)?
+            };
+            records.push(output);
+
+            match line_ending::<_, Error>(next) {
+                Ok((after, _)) if has_next_line(after) => rest = after,
+                _ => return Ok((next, records)),
+            }
+        }
+    }
+}
+
+/// Blocks of lines separated by one or more blank lines, e.g. the rules and the updates of a day.
+pub fn blocks<'a, O>(
+    block: impl Parser<&'a str, O, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(pair(line_ending, many1(line_ending)), block)
+}
+
+/// A value labelled with a key, e.g. `190: 10 19`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, Error<'a>>,
+    value: impl Parser<&'a str, V, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, (K, V)> {
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// A grid with one row per line, mapping every character to a cell. Ends before a blank line.
+/// Characters rejected by `cell` and rows of different widths are errors.
+pub fn grid<'a, T>(
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Grid<T>> {
+    move |input| {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+        let mut rest = input;
+
+        loop {
+            let row = many1(map_opt(none_of("\r\n"), &mut cell))(rest);
+            let (next, row) = if height == 0 {
+                row?
+            } else {
+                // once the first row is parsed, the input is known to be a grid.
+                row.map_err(cut_error)?
+            };
+
+            if !next.is_empty() && !next.starts_with(['\n', '\r']) {
+                return Err(nom::Err::Failure(Error::new(next, "unexpected character")));
+            }
+
+            if height == 0 {
+                width = row.len();
+            } else if row.len() != width {
+                return Err(nom::Err::Failure(Error::new(
+                    rest,
+                    format!("row has {} cells instead of {width}", row.len()),
+                )));
+            }
+            cells.extend(row);
+            height += 1;
+
+            match line_ending::<_, Error>(next) {
+                Ok((after, _)) if has_next_line(after) => rest = after,
+                _ => {
+                    let grid = Grid::from_cells(width, height, cells)
+                        .expect("rows are checked to be of equal width");
+                    return Ok((next, grid));
+                }
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::alpha1;
+    use nom::error::context;
+    use nom::sequence::separated_pair;
+
+    use super::{
+        all, blocks, grid, key_value, lines, signed, signed_list, unsigned, unsigned_list,
+        InputError,
+    };
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(all("42", unsigned::<u32>), Ok(42));
+        assert_eq!(all("-42", signed::<i64>), Ok(-42));
+        assert_eq!(all("+42", signed::<i8>), Ok(42));
+        assert_eq!(all("1,2, 3", unsigned_list::<u8>), Ok(vec![1, 2, 3]));
+        assert_eq!(all("7   -2\t+3", signed_list::<i32>), Ok(vec![7, -2, 3]));
+
+        let err = all("300", unsigned::<u8>).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: number too large to fit in target type, found `300`"
+        );
+        assert!(all("-1", unsigned::<u32>).is_err());
+    }
+
+    #[test]
+    fn ignores_trailing_newlines() {
+        let expected = Ok(vec![vec![125, 17]]);
+        assert_eq!(all("125 17", lines(unsigned_list::<u64>)), expected);
+        assert_eq!(all("125 17\n", lines(unsigned_list::<u64>)), expected);
+        assert_eq!(all("125 17\r\n\n", lines(unsigned_list::<u64>)), expected);
+    }
+
+    #[test]
+    fn parses_records() {
+        let input = "190: 10 19\n3267: 81 40 27\n";
+        assert_eq!(
+            all(
+                input,
+                lines(key_value(unsigned::<u64>, unsigned_list::<u64>))
+            ),
+            Ok(vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])])
+        );
+
+        let input = "3   4\r\n4   3\r\n";
+        assert_eq!(
+            all(input, lines(unsigned_list::<u32>)),
+            Ok(vec![vec![3, 4], vec![4, 3]])
+        );
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let input = "47|53\n97|13\n\n75,47,61\n97,61\n";
+        let rules = lines(separated_pair(unsigned::<u32>, tag("|"), unsigned));
+        let updates = lines(unsigned_list::<u32>);
+        assert_eq!(
+            all(input, separated_pair(rules, tag("\n\n"), updates)),
+            Ok((
+                vec![(47, 53), (97, 13)],
+                vec![vec![75, 47, 61], vec![97, 61]]
+            ))
+        );
+
+        let input = "1\n2\n\n3\n\n\n4\n";
+        assert_eq!(
+            all(input, blocks(lines(unsigned::<u32>))),
+            Ok(vec![vec![1, 2], vec![3], vec![4]])
+        );
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grids = all("#.\n.#\n\n..\n..\n", blocks(grid(|c| Some(c == '#')))).unwrap();
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[0].to_string(), "truefalse\nfalsetrue");
+
+        let digits = all("12\n34\n", grid(|c| c.to_digit(10))).unwrap();
+        assert_eq!(digits[(1, 1)], 4);
+
+        assert_eq!(
+            all("12\n3x\n", grid(|c| c.to_digit(10))),
+            Err(InputError {
+                line: 2,
+                column: 2,
+                message: "unexpected character".into(),
+                found: "x".into(),
+            })
+        );
+
+        let err = all("123\n45\n", grid(|c| c.to_digit(10))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: row has 2 cells instead of 3, found `45`"
+        );
+    }
+
+    #[test]
+    fn reports_positions() {
+        let err = all("1 2\n3 x 4\n", lines(unsigned_list::<u32>)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: unexpected trailing input, found `x 4`"
+        );
+
+        let err = all(
+            "1: 2\nfoo: 3",
+            lines(key_value(unsigned::<u32>, unsigned::<u32>)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a number, found `foo: 3`"
+        );
+
+        let err = all("1\n2\n\n3\n300\n", blocks(lines(unsigned::<u8>))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 1: number too large to fit in target type, found `300`"
+        );
+
+        let err = all(
+            "abc",
+            context("expected a calibration", key_value(alpha1, unsigned::<u32>)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected a calibration: unexpected input, found end of line"
+        );
+
+        let err = all("1 abcdefghijklmnopqrstuvwxyz", unsigned_list::<u32>).unwrap_err();
+        assert!(err.to_string().ends_with("found ` abcdefghijklmnopqrs…`"));
+    }
+}
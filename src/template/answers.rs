@@ -0,0 +1,324 @@
+/// Store of accepted answers, used to detect regressions.
+/// Answers for a day live in `data/<year>/answers/NN.json`.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::run_multi::{DayRun, DayStatus};
+use crate::template::{data_dir, Day, Year};
+
+/// Accepted answers for both parts of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Outcome of verifying a run of a day against the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The part reproduced its accepted answer.
+    Verified { part: u8 },
+    /// The part no longer reproduces its accepted answer, or, without a part, the day did not run to completion.
+    Regressed { part: Option<u8>, reason: String },
+    /// The part returned an answer, but none has been accepted yet.
+    Unverified { part: u8 },
+}
+
+/// Result of checking an answer against the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the accepted answer.
+    Correct,
+    /// The answer differs from the accepted answer.
+    Incorrect { expected: String },
+    /// There is no accepted answer for this part yet.
+    Unknown,
+}
+
+impl Answers {
+    /// Read the accepted answers for a day. If no answers were recorded, returns empty answers.
+    pub fn read(year: Year, day: Day) -> Result<Self, String> {
+        Self::read_path(&get_answers_path(year, day))
+    }
+
+    /// Read answers from a file. If the file does not exist, returns empty answers.
+    pub fn read_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Answers::try_from(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Persist the accepted answers for a day.
+    pub fn store(&self, year: Year, day: Day) -> Result<(), io::Error> {
+        let path = get_answers_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part_1 = Some(answer),
+            2 => self.part_2 = Some(answer),
+            _ => {}
+        }
+    }
+
+    /// Compare an answer of `part` with the accepted answer.
+    /// A missing answer counts as incorrect if an accepted answer exists.
+    pub fn check(&self, part: u8, answer: Option<&str>) -> Verdict {
+        match self.get(part) {
+            None => Verdict::Unknown,
+            Some(expected) if Some(expected) == answer => Verdict::Correct,
+            Some(expected) => Verdict::Incorrect {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    /// Check the answers of a run against the accepted answers.
+    /// A day that panicked, timed out or failed is a regression, and so is an accepted part that reported no answer.
+    pub fn verify(&self, day_run: &DayRun) -> Vec<Check> {
+        let reason = match &day_run.status {
+            DayStatus::Solved | DayStatus::Unsolved => None,
+            DayStatus::Missing if *self == Answers::default() => return vec![],
+            DayStatus::Missing => Some("accepted answers exist, but no solution".into()),
+            DayStatus::Panicked(message) => Some(format!(
+                "panicked: {}",
+                message.as_deref().unwrap_or("no message")
+            )),
+            DayStatus::TimedOut(timeout) => Some(format!("timed out after {timeout:?}")),
+            DayStatus::Failed(reason) => Some(format!("failed: {reason}")),
+        };
+
+        if let Some(reason) = reason {
+            return vec![Check::Regressed { part: None, reason }];
+        }
+
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                let answer = day_run
+                    .reports
+                    .iter()
+                    .find(|r| r.part == part)
+                    .and_then(|r| r.answer.as_deref());
+
+                match self.check(part, answer) {
+                    Verdict::Correct => Some(Check::Verified { part }),
+                    Verdict::Incorrect { expected } => Some(Check::Regressed {
+                        part: Some(part),
+                        reason: match answer {
+                            Some(answer) => format!("expected {expected}, got {answer}"),
+                            None => format!("expected {expected}, but no answer was reported"),
+                        },
+                    }),
+                    Verdict::Unknown => answer.map(|_| Check::Unverified { part }),
+                }
+            })
+            .collect()
+    }
+}
+
+fn get_answers_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("answers").join(format!("{day}.json"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let to_json = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("part_1".into(), to_json(&value.part_1));
+        map.insert("part_2".into(), to_json(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let part = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("expected `{key}` to be null or string.")),
+        };
+
+        Ok(Answers {
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Check, Verdict};
+    use crate::day;
+    use crate::template::report::{PartReport, PartStatus};
+    use crate::template::run_multi::{DayRun, DayStatus};
+    use tinyjson::JsonValue;
+
+    fn day_run(answers: [Option<&str>; 2], status: DayStatus) -> DayRun {
+        let reports = answers
+            .iter()
+            .zip(1..)
+            .map(|(answer, part)| PartReport {
+                part,
+                answer: answer.map(Into::into),
+                duration_nanos: 1,
+                samples: 1,
+                stats: None,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+            })
+            .collect();
+
+        DayRun {
+            day: day!(1),
+            reports,
+            status,
+        }
+    }
+
+    fn accepted() -> Answers {
+        Answers {
+            part_1: Some("11".into()),
+            part_2: None,
+        }
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        };
+
+        assert_eq!(answers.check(1, Some("42")), Verdict::Correct);
+        assert_eq!(
+            answers.check(1, Some("43")),
+            Verdict::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            answers.check(1, None),
+            Verdict::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(2, Some("1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = Answers {
+            part_1: Some("multi\nline".into()),
+            part_2: Some("7".into()),
+        };
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn handles_partial_answers() {
+        let answers = Answers::try_from(r#"{ "part_1": "1" }"#.to_string()).unwrap();
+        assert_eq!(answers.part_1, Some("1".into()));
+        assert_eq!(answers.part_2, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "part_1": 1 }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn verifies_runs() {
+        let run = day_run([Some("11"), Some("31")], DayStatus::Solved);
+        assert_eq!(
+            accepted().verify(&run),
+            vec![Check::Verified { part: 1 }, Check::Unverified { part: 2 }]
+        );
+
+        let run = day_run([Some("12"), None], DayStatus::Unsolved);
+        assert_eq!(
+            accepted().verify(&run),
+            vec![Check::Regressed {
+                part: Some(1),
+                reason: "expected 11, got 12".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn verifies_parts_without_reports() {
+        let mut run = day_run([None, None], DayStatus::Unsolved);
+        run.reports.clear();
+        assert_eq!(
+            accepted().verify(&run),
+            vec![Check::Regressed {
+                part: Some(1),
+                reason: "expected 11, but no answer was reported".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn verifies_panicked_days() {
+        let mut run = day_run([None, None], DayStatus::Panicked(Some("overflow".into())));
+        run.reports.clear();
+        assert_eq!(
+            accepted().verify(&run),
+            vec![Check::Regressed {
+                part: None,
+                reason: "panicked: overflow".into()
+            }]
+        );
+
+        let run = day_run([None, None], DayStatus::Failed("exit status: 101".into()));
+        assert_eq!(Answers::default().verify(&run).len(), 1);
+
+        let run = day_run([None, None], DayStatus::Missing);
+        assert!(Answers::default().verify(&run).is_empty());
+        assert_eq!(accepted().verify(&run).len(), 1);
+    }
+}
@@ -0,0 +1,471 @@
+/// Client for the Advent of Code website.
+///
+/// The session cookie is read from `AOC_SESSION`, or from the file at `AOC_SESSION_FILE`, defaulting to
+/// `~/.adventofcode.session`. The base URL can be overridden with `AOC_BASE_URL`, e.g. to point at a local server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{Day, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/scottroemeschke/aoc-rust-2024";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    InvalidUrl(String),
+    IO(io::Error),
+    TransportFailed(String),
+    BadStatus(u16, String),
+    MalformedResponse(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or store it in `~/.adventofcode.session`."
+            ),
+            AocClientError::InvalidUrl(url) => write!(f, "invalid base URL `{url}`."),
+            AocClientError::IO(e) => write!(f, "request failed: {e}"),
+            AocClientError::TransportFailed(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            AocClientError::MalformedResponse(e) => write!(f, "malformed response: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// Hint given by the server for a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Verdict of the server on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+    },
+    /// An answer was submitted too recently, `wait` holds the remaining time as stated by the server.
+    TooRecent {
+        wait: Option<String>,
+    },
+    /// The part is locked or has already been solved.
+    WrongLevel,
+    Unknown,
+}
+
+/// Result of a submission, including the plain-text message of the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub outcome: SubmissionOutcome,
+    pub message: String,
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Create a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Download the personal puzzle input for a day.
+    pub fn get_input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.send("GET", &day_path(year, day, "/input"), None)
+    }
+
+    /// Download the puzzle description for a day, as the HTML of its `<article>` elements.
+    pub fn get_puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let html = self.send("GET", &day_path(year, day, ""), None)?;
+        let articles = extract_elements(&html, "article");
+
+        if articles.is_empty() {
+            return Err(AocClientError::MalformedResponse(
+                "puzzle page does not contain a description.".into(),
+            ));
+        }
+
+        Ok(articles.join("\n\n"))
+    }
+
+    /// Submit an answer for a part and interpret the response of the server.
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let body = format!("level={part}&answer={}", url_encode(answer));
+        let html = self.send("POST", &day_path(year, day, "/answer"), Some(&body))?;
+
+        let message = extract_elements(&html, "article")
+            .first()
+            .map(|x| html_to_text(x))
+            .ok_or_else(|| {
+                AocClientError::MalformedResponse("answer page does not contain a message.".into())
+            })?;
+
+        Ok(Submission {
+            outcome: parse_outcome(&message),
+            message,
+        })
+    }
+
+    fn send(&self, method: &str, path: &str, body: Option<&str>) -> Result<String, AocClientError> {
+        if !self.base_url.starts_with("http://") && !self.base_url.starts_with("https://") {
+            return Err(AocClientError::InvalidUrl(self.base_url.clone()));
+        }
+
+        let agent = ureq::AgentBuilder::new()
+            .timeout(TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build();
+
+        let request = agent
+            .request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session));
+
+        let result = if let Some(body) = body {
+            request
+                .set("Content-Type", "application/x-www-form-urlencoded")
+                .send_string(body)
+        } else {
+            request.call()
+        };
+
+        match result {
+            Ok(response) if response.status() == 200 => Ok(response.into_string()?),
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                let status = response.status();
+                Err(AocClientError::BadStatus(status, response.into_string()?))
+            }
+            Err(ureq::Error::Transport(e)) => Err(AocClientError::TransportFailed(e.to_string())),
+        }
+    }
+}
+
+pub fn get_input_path(year: Year, day: Day) -> String {
+    format!("data/{year}/inputs/{day}.txt")
+}
+
+/// Path of the downloaded puzzle description, stored as the HTML returned by [`AocClient::get_puzzle`].
+pub fn get_puzzle_path(year: Year, day: Day) -> String {
+    format!("data/{year}/puzzles/{day}.html")
+}
+
+fn day_path(year: Year, day: Day, suffix: &str) -> String {
+    format!("/{year}/day/{}{suffix}", day.into_inner())
+}
+
+fn get_session() -> Option<String> {
+    if let Some(session) = env::var("AOC_SESSION")
+        .ok()
+        .filter(|x| !x.trim().is_empty())
+    {
+        return Some(session);
+    }
+
+    let path = env::var("AOC_SESSION_FILE")
+        .map(PathBuf::from)
+        .or_else(|_| {
+            env::var("HOME").map(|home| PathBuf::from(home).join(".adventofcode.session"))
+        });
+
+    fs::read_to_string(path.ok()?)
+        .ok()
+        .filter(|x| !x.trim().is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_outcome(message: &str) -> SubmissionOutcome {
+    if message.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if message.contains("That's not the right answer") {
+        let hint = if message.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if message.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        SubmissionOutcome::Incorrect { hint }
+    } else if message.contains("You gave an answer too recently") {
+        let wait = message
+            .split("You have ")
+            .nth(1)
+            .and_then(|x| x.split(" left to wait").next())
+            .map(str::to_string);
+        SubmissionOutcome::TooRecent { wait }
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::WrongLevel
+    } else {
+        SubmissionOutcome::Unknown
+    }
+}
+
+/// Extract the inner HTML of all (non-nested) elements with the given tag name.
+pub fn extract_elements(html: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+
+    let mut elements = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        // skip tags that merely start with the name, e.g. `<articles>` when looking for `<article>`.
+        let after_name = rest[start + open.len()..].chars().next();
+        if !after_name.is_some_and(|c| c.is_whitespace() || c == '>' || c == '/') {
+            rest = &rest[start + open.len()..];
+            continue;
+        }
+
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..]
+            .find(&close)
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+        elements.push(rest[content_start..end].to_string());
+        rest = &rest[end + close.len()..];
+    }
+
+    elements
+}
+
+/// Render HTML as plain text by removing tags and decoding common entities.
+pub fn html_to_text(html: &str) -> String {
+    strip_tags(html).trim().to_string()
+}
+
+/// Remove tags and decode common entities, preserving all whitespace.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        extract_elements, html_to_text, AocClient, AocClientError, Hint, SubmissionOutcome,
+    };
+    use crate::{day, template::Year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    fn year() -> Year {
+        Year::new(2024).unwrap()
+    }
+
+    /// Stand-in server that answers a single request with `status` and `body`, returning the raw request.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: text/html\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{body}\r\n0\r\n\r\n",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, server) = serve_once("200 OK", "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(client.get_input(year(), day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2></article><p>x</p><article><p>Part two</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let puzzle = client.get_puzzle(year(), day!(1)).unwrap();
+        assert_eq!(puzzle, "<h2>--- Day 1 ---</h2>\n\n<p>Part two</p>");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let submission = client.submit(year(), day!(3), 2, "a b&c").unwrap();
+        assert_eq!(
+            submission.outcome,
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooHigh)
+            }
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=a%20b%26c"));
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        use super::parse_outcome;
+
+        assert_eq!(
+            parse_outcome("That's the right answer! You are one gold star closer."),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            parse_outcome("That's not the right answer; your answer is too low."),
+            SubmissionOutcome::Incorrect {
+                hint: Some(Hint::TooLow)
+            }
+        );
+        assert_eq!(
+            parse_outcome("That's not the right answer."),
+            SubmissionOutcome::Incorrect { hint: None }
+        );
+        assert_eq!(
+            parse_outcome("You gave an answer too recently. You have 38s left to wait."),
+            SubmissionOutcome::TooRecent {
+                wait: Some("38s".into())
+            }
+        );
+        assert_eq!(
+            parse_outcome(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            SubmissionOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn surfaces_bad_status() {
+        let (base_url, server) = serve_once("404 Not Found", "Not found");
+        let client = AocClient::new(&base_url, "secret");
+
+        match client.get_input(year(), day!(25)) {
+            Err(AocClientError::BadStatus(404, body)) => assert_eq!(body, "Not found"),
+            _ => panic!("expected bad status"),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn extracts_elements_by_exact_tag_name() {
+        let html = "<articles>x</articles><article-foo>y</article-foo><article class=\"a\">z</article><article>w</article>";
+        assert_eq!(extract_elements(html, "article"), vec!["z", "w"]);
+    }
+
+    #[test]
+    fn renders_html_as_text() {
+        assert_eq!(
+            html_to_text("<p>Use <code>a &lt; b</code> &amp; <em>go</em>!</p>"),
+            "Use a < b & go!"
+        );
+    }
+}
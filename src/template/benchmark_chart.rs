@@ -0,0 +1,331 @@
+/// Renders stored timings as a self-contained SVG bar chart, one stacked bar per day.
+use std::{fmt::Write, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{
+    data_dir,
+    timings::{PartTiming, Timing, Timings},
+    Year,
+};
+
+const BAR_WIDTH: f64 = 24.0;
+const BAR_GAP: f64 = 12.0;
+const PLOT_HEIGHT: f64 = 240.0;
+/// Leaves room for the title and legend if only a few days are benched.
+const MIN_PLOT_WIDTH: f64 = 264.0;
+const MARGIN_LEFT: f64 = 72.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 64.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+
+/// Segments of a bar from bottom to top, with their legend label and fill color.
+const SERIES: [(&str, &str); 3] = [
+    ("Parse", "#9e9e9e"),
+    ("Part 1", "#4e79a7"),
+    ("Part 2", "#f28e2b"),
+];
+
+/// Scale of the y-axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Scale {
+    #[default]
+    Linear,
+    /// Logarithmic, with one tick per power of ten. Useful if the timings of days differ by orders of magnitude.
+    Log,
+}
+
+/// Path of the chart relative to the repository root, as referenced from the readme.
+pub fn get_readme_path(year: Year) -> String {
+    format!("./data/{year}/benchmarks.svg")
+}
+
+fn get_chart_path(year: Year) -> PathBuf {
+    data_dir(year).join("benchmarks.svg")
+}
+
+/// Render the chart of a year to `data/<year>/benchmarks.svg`.
+pub fn store(year: Year, timings: &Timings, scale: Scale) -> Result<(), io::Error> {
+    let path = get_chart_path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, render(year, timings, scale))
+}
+
+/// Segments of a bar, leaving out failed parts.
+fn segments(timing: &Timing) -> [Option<PartTiming>; 3] {
+    [timing.parse, timing.part_1, timing.part_2].map(|p| p.filter(|p| !p.failed))
+}
+
+/// Maps nanoseconds to a y offset from the bottom of the plot area.
+struct Axis {
+    scale: Scale,
+    min: f64,
+    max: f64,
+    ticks: Vec<f64>,
+}
+
+impl Axis {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(timings: &Timings, scale: Scale) -> Self {
+        let totals: Vec<f64> = timings
+            .data
+            .iter()
+            .map(|t| t.total_nanos() as f64)
+            .filter(|&t| t > 0.0)
+            .collect();
+
+        let max = totals.iter().copied().fold(0.0, f64::max);
+
+        match scale {
+            Scale::Linear => {
+                let step = nice_step(max.max(1000.0) / 5.0);
+                let max = (max / step).ceil().max(1.0) * step;
+                let ticks = (0..)
+                    .map(|i| f64::from(i) * step)
+                    .take_while(|&t| t <= max)
+                    .collect();
+
+                Self {
+                    scale,
+                    min: 0.0,
+                    max,
+                    ticks,
+                }
+            }
+            Scale::Log => {
+                // the smallest segment decides where the axis starts, so that every segment stays visible.
+                // it starts at the largest power of ten strictly below it, a segment at the axis start would be invisible.
+                let smallest = timings
+                    .data
+                    .iter()
+                    .flat_map(segments)
+                    .flatten()
+                    .map(|p| p.nanos as f64)
+                    .filter(|&n| n > 0.0)
+                    .fold(f64::INFINITY, f64::min);
+
+                let min_exp = if smallest.is_finite() {
+                    smallest.log10().ceil() - 1.0
+                } else {
+                    0.0
+                };
+                let max_exp = max.log10().ceil().max(min_exp + 1.0);
+
+                #[allow(clippy::cast_possible_truncation)]
+                let ticks = (min_exp as i32..=max_exp as i32)
+                    .map(|e| 10_f64.powi(e))
+                    .collect();
+
+                Self {
+                    scale,
+                    min: 10_f64.powf(min_exp),
+                    max: 10_f64.powf(max_exp),
+                    ticks,
+                }
+            }
+        }
+    }
+
+    fn height(&self, nanos: f64) -> f64 {
+        let ratio = match self.scale {
+            Scale::Linear => nanos / self.max,
+            Scale::Log if nanos <= self.min => 0.0,
+            Scale::Log => (nanos / self.min).log10() / (self.max / self.min).log10(),
+        };
+        ratio.clamp(0.0, 1.0) * PLOT_HEIGHT
+    }
+}
+
+/// Round a tick step up to 1, 2 or 5 times a power of ten.
+fn nice_step(raw: f64) -> f64 {
+    let magnitude = 10_f64.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|f| f * magnitude)
+        .find(|&step| step >= raw)
+        .unwrap_or(10.0 * magnitude)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Render the timings of a year as an SVG document.
+#[allow(clippy::cast_precision_loss)]
+pub fn render(year: Year, timings: &Timings, scale: Scale) -> String {
+    let axis = Axis::new(timings, scale);
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+
+    let plot_width =
+        ((timings.data.len() as f64) * (BAR_WIDTH + BAR_GAP) + BAR_GAP).max(MIN_PLOT_WIDTH);
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let baseline = MARGIN_TOP + PLOT_HEIGHT;
+
+    let mut svg = String::new();
+
+    // writing to a `String` cannot fail.
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN_LEFT}" y="24" font-size="16" font-weight="bold">{year} Benchmarks{}</text>"#,
+        if scale == Scale::Log {
+            " (log scale)"
+        } else {
+            ""
+        }
+    );
+
+    // legend
+    let mut x = MARGIN_LEFT;
+    for (i, (label, color)) in SERIES.iter().enumerate() {
+        if i == 0 && !has_parse {
+            continue;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="36" width="12" height="12" fill="{color}"/><text x="{}" y="46">{label}</text>"#,
+            x + 16.0
+        );
+        x += 80.0;
+    }
+
+    // y-axis with grid lines
+    for tick in &axis.ticks {
+        let y = baseline - axis.height(*tick);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{y:.1}" x2="{:.1}" y2="{y:.1}" stroke="#e0e0e0"/><text x="{}" y="{:.1}" text-anchor="end">{}</text>"##,
+            MARGIN_LEFT + plot_width,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            format_nanos(*tick)
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{MARGIN_TOP}" x2="{MARGIN_LEFT}" y2="{baseline}" stroke="#616161"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{baseline}" x2="{:.1}" y2="{baseline}" stroke="#616161"/>"##,
+        MARGIN_LEFT + plot_width
+    );
+
+    // one stacked bar per day
+    for (i, timing) in timings.data.iter().enumerate() {
+        let x = MARGIN_LEFT + BAR_GAP + (i as f64) * (BAR_WIDTH + BAR_GAP);
+        let mut total = 0.0;
+
+        for ((label, color), part) in SERIES.iter().zip(segments(timing)) {
+            let Some(part) = part else {
+                continue;
+            };
+
+            let bottom = axis.height(total);
+            total += part.nanos as f64;
+            let top = axis.height(total);
+
+            if top - bottom <= 0.0 {
+                continue;
+            }
+
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x:.1}" y="{:.1}" width="{BAR_WIDTH}" height="{:.1}" fill="{color}"><title>Day {} {label}: {:.1?}</title></rect>"#,
+                baseline - top,
+                top - bottom,
+                timing.day,
+                part.duration()
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            x + BAR_WIDTH / 2.0,
+            baseline + 18.0,
+            timing.day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.1}" y="{}" text-anchor="middle">Day</text>"#,
+        MARGIN_LEFT + plot_width / 2.0,
+        height - 6.0
+    );
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{nice_step, render, Scale};
+    use crate::template::{
+        fixtures::{get_mock_timings, part, year},
+        timings::Timings,
+    };
+
+    #[test]
+    fn rounds_tick_steps() {
+        assert_eq!(nice_step(0.9), 1.0);
+        assert_eq!(nice_step(1.5), 2.0);
+        assert_eq!(nice_step(3000.0), 5000.0);
+        assert_eq!(nice_step(7.0), 10.0);
+    }
+
+    #[test]
+    fn renders_stacked_bars() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+
+        let svg = render(year(), &timings, Scale::Linear);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("2024 Benchmarks</text>"));
+        assert!(svg.contains("<title>Day 01 Part 1: 10.0ms</title>"));
+        assert!(svg.contains("<title>Day 01 Part 2: 20.0ms</title>"));
+        assert!(svg.contains("<title>Day 02 Part 1: 30.0ms</title>"));
+        assert!(!svg.contains("Day 02 Part 2"));
+        // no parse step was timed, so the legend omits it.
+        assert!(!svg.contains(">Parse</text>"));
+        // day 4 fills the axis, which ends at the next tick.
+        assert!(svg.contains(">100ms</text>"));
+    }
+
+    #[test]
+    fn renders_log_scale() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1 = part(10_000);
+
+        let svg = render(year(), &timings, Scale::Log);
+        assert!(svg.contains("(log scale)"));
+        for tick in ["1µs", "10µs", "100µs", "1ms", "10ms", "100ms"] {
+            assert!(
+                svg.contains(&format!(">{tick}</text>")),
+                "missing tick {tick}"
+            );
+        }
+        // with a log scale, the small parts of day 1 are still visible.
+        assert!(svg.contains("<title>Day 01 Part 1: 10.0µs</title>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(year(), &Timings::default(), Scale::Log);
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(!svg.contains("<title>"));
+    }
+}
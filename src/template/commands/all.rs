@@ -1,5 +1,9 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{print_summary, run_multi};
+use crate::template::{all_days, Year};
+
+pub fn handle(year: Year, is_release: bool, jobs: usize, timeout: Option<Duration>) {
+    let run = run_multi(year, &all_days().collect(), is_release, None, jobs, timeout);
+    print_summary(&run.days);
 }
@@ -1,14 +1,44 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    aoc_client::{get_input_path, get_puzzle_path, AocClient},
+    commands::scaffold,
+    Day, Year,
+};
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(year: Year, day: Day) {
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to download: {e}");
         process::exit(1);
-    }
+    });
+
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let input = client.get_input(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to download input: {e}");
         process::exit(1);
+    });
+
+    let puzzle = client.get_puzzle(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to download puzzle: {e}");
+        process::exit(1);
+    });
+
+    let write = |path: &str, contents: String| {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, contents)
     };
+
+    if let Err(e) = write(&input_path, input).and_then(|()| write(&puzzle_path, puzzle)) {
+        eprintln!("Failed to write files: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+
+    scaffold::apply_puzzle(year, day);
 }
@@ -1,15 +1,33 @@
-use std::process;
+use std::{fs, path::Path, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_client::{get_puzzle_path, html_to_text, AocClient},
+    commands::scaffold,
+    Day, Year,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(year: Year, day: Day) {
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to read puzzle: {e}");
         process::exit(1);
-    }
+    });
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let puzzle = client.get_puzzle(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read puzzle: {e}");
         process::exit(1);
-    };
+    });
+
+    let puzzle_path = get_puzzle_path(year, day);
+    let write = Path::new(&puzzle_path)
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&puzzle_path, &puzzle));
+
+    if let Err(e) = write {
+        eprintln!("Failed to write puzzle file: {e}");
+    }
+
+    println!("{}", html_to_text(&puzzle));
+
+    scaffold::apply_puzzle(year, day);
 }
@@ -1,13 +1,19 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    aoc_client::get_puzzle_path,
+    bin_name,
+    puzzle::{
+        extract_answer, extract_example, extract_part_example, extract_title, fill_test_answer,
+    },
+    templates::{self, Variables},
+    Day, Year,
+};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,18 +25,64 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Create an empty file, keeping an existing one. Returns whether the file was created.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(
+    year: Year,
+    day: Day,
+    overwrite: bool,
+    template: Option<&str>,
+    answer_type: Option<String>,
+    split_examples: bool,
+) {
+    let input_path = format!("data/{year}/inputs/{day}.txt");
+    let example_paths = if split_examples {
+        vec![
+            format!("data/{year}/examples/{day}-1.txt"),
+            format!("data/{year}/examples/{day}-2.txt"),
+        ]
+    } else {
+        vec![format!("data/{year}/examples/{day}.txt")]
+    };
+    let module_path = format!("src/bin/{}.rs", bin_name(year, day));
+
+    let template = templates::load(template).unwrap_or_else(|e| {
+        eprintln!("Failed to load template: {e}");
+        process::exit(1);
+    });
+
+    let title = fs::read_to_string(get_puzzle_path(year, day))
+        .ok()
+        .and_then(|puzzle| extract_title(&puzzle));
+    let (mut module, unknown) =
+        templates::render(&template, &Variables::new(year, day, title, answer_type));
+
+    for name in unknown {
+        eprintln!("Unknown template variable `%{name}%`, it was left as is.");
+    }
+
+    if split_examples {
+        match templates::split_example_tests(&module) {
+            Some(split) => module = split,
+            None => eprintln!(
+                "The tests of the template do not read the example, they were not changed to read split examples."
+            ),
+        }
+    }
+
+    for dir in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(format!("data/{year}/{dir}")) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +92,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -55,25 +103,116 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {
+            println!("Kept existing input file \"{}\"", &input_path);
+        }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    for example_path in &example_paths {
+        match create_file(example_path) {
+            Ok(true) => {
+                println!("Created empty example file \"{example_path}\"");
+            }
+            Ok(false) => {
+                println!("Kept existing example file \"{example_path}\"");
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
+    apply_puzzle(year, day);
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if Year::from_env() == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+}
+
+/// Fill the example files and the example tests of a scaffolded day from its downloaded puzzle description.
+/// Only empty example files and tests that still assert the scaffolded `None` are changed.
+/// Days with split examples (`<day>-1.txt`, `<day>-2.txt`) get the example of each part.
+pub fn apply_puzzle(year: Year, day: Day) {
+    let Ok(puzzle) = fs::read_to_string(get_puzzle_path(year, day)) else {
+        return;
+    };
+
+    let module_path = format!("src/bin/{}.rs", bin_name(year, day));
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let part_example_paths = [1, 2].map(|part| format!("data/{year}/examples/{day}-{part}.txt"));
+    let split = part_example_paths
+        .iter()
+        .any(|path| Path::new(path).exists());
+
+    let examples = if split {
+        vec![
+            (
+                part_example_paths[0].clone(),
+                extract_part_example(&puzzle, 1),
+            ),
+            (
+                part_example_paths[1].clone(),
+                extract_part_example(&puzzle, 2),
+            ),
+        ]
+    } else {
+        vec![(
+            format!("data/{year}/examples/{day}.txt"),
+            extract_example(&puzzle),
+        )]
+    };
+
+    for (path, example) in examples {
+        if fs::read_to_string(&path).is_ok_and(|s| !s.trim().is_empty()) {
+            continue;
+        }
+
+        match example {
+            Some(example) => match fs::write(&path, example) {
+                Ok(()) => println!("Wrote the example of the puzzle to \"{}\"", &path),
+                Err(e) => {
+                    eprintln!("Failed to write example file: {e}");
+                    process::exit(1);
+                }
+            },
+            None => println!("Could not find an example for \"{}\" in the puzzle.", &path),
+        }
+    }
+
+    let mut changed = false;
+    for part in [1, 2] {
+        match extract_answer(&puzzle, part, split) {
+            Ok(answer) => {
+                if let Some(filled) = fill_test_answer(&module, part, &answer) {
+                    println!("Set the expected example answer of part {part} to {answer}");
+                    module = filled;
+                    changed = true;
+                }
+            }
+            Err(reason) => {
+                println!("Could not extract the example answer of part {part}: {reason}.");
+            }
+        }
+    }
+
+    if changed {
+        if let Err(e) = fs::write(&module_path, module) {
+            eprintln!("Failed to write module file: {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -1,9 +1,75 @@
-use std::process::{Command, Stdio};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
 
-use crate::template::Day;
+use crate::template::corpus::{self, CorpusRun};
+use crate::template::run_multi::child_commands::{self, RunOptions};
+use crate::template::{
+    bin_name, data_dir, runner::BenchConfig, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Interval in which watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Time without further changes before a rerun starts, so that a burst of saves triggers a single run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    accept_part: Option<u8>,
+    bench: Option<BenchConfig>,
+    input: Option<String>,
+    example: bool,
+) {
+    let mut cmd_args = get_run_args(year, day, release, dhat);
+
+    if let Some(submit_part) = submit_part {
+        cmd_args.push("--submit".to_string());
+        cmd_args.push(submit_part.to_string());
+
+        if force {
+            cmd_args.push("--force".to_string());
+        }
+    }
+
+    if let Some(accept_part) = accept_part {
+        cmd_args.push("--accept".to_string());
+        cmd_args.push(accept_part.to_string());
+    }
+
+    if let Some(bench) = bench {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
+    }
+
+    cmd_args.extend(get_input_args(input, example));
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Arguments of `cargo run` for the binary of a day, up to the `--` that separates the arguments of the solution.
+fn get_run_args(year: Year, day: Day, release: bool, dhat: bool) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
 
     if dhat {
         cmd_args.extend([
@@ -17,18 +83,179 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args
+}
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+fn get_input_args(input: Option<String>, example: bool) -> Vec<String> {
+    let mut cmd_args = vec![];
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    if example {
+        cmd_args.push("--example".to_string());
+    }
+
+    cmd_args
+}
+
+/// Run both parts against the puzzle input and every input of the day's corpus, and print a matrix of the answers.
+/// Exits with a non-zero status if any answer differs from the expected one or a run did not complete.
+pub fn corpus(year: Year, day: Day, release: bool) {
+    let inputs = corpus::read_inputs(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read corpus: {e}");
+        process::exit(1);
+    });
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs found. Add input files to `{}`.",
+            corpus::get_corpus_dir(year, day).display()
+        );
+        process::exit(1);
+    }
+
+    // build once up front, so compile errors are not hidden in the matrix.
+    let executables = child_commands::build_solutions(year, &[day], release);
+    let Some(executable) = executables.get(&day) else {
+        eprintln!("Failed to build the solution of day {day}.");
+        process::exit(1);
+    };
+
+    let runs: Vec<CorpusRun> = inputs
+        .into_iter()
+        .map(|input| {
+            println!("Running {}...", input.name);
+
+            let options = RunOptions {
+                input: Some(&input.path),
+                ..RunOptions::default()
+            };
+            let run = child_commands::run_solution(year, day, Some(executable), &options, &|_| {});
+
+            CorpusRun { input, run }
+        })
+        .collect();
+
+    println!("\n{ANSI_BOLD}Day {day} corpus{ANSI_RESET}");
+    println!("-------------");
+    println!("{}", corpus::format_matrix(&runs));
+
+    let disagreements = runs.iter().filter(|r| r.is_disagreement()).count();
+    if disagreements > 0 {
+        eprintln!("\n{disagreements} input(s) disagree with their expected answers.");
+        process::exit(1);
+    }
+}
+
+/// Rerun a day whenever its solution, input or examples change, until interrupted.
+/// With `test`, the tests of the day run after the solution. Failed builds and runs do not end the watch.
+pub fn watch(
+    year: Year,
+    day: Day,
+    release: bool,
+    input: Option<String>,
+    example: bool,
+    test: bool,
+) {
+    if input.as_deref() == Some("-") {
+        eprintln!("--watch cannot read the input from stdin. Pass a file to --input instead.");
+        process::exit(1);
+    }
+
+    let input_path = input.as_ref().map(PathBuf::from);
+
+    let mut run_args = get_run_args(year, day, release, false);
+    run_args.extend(get_input_args(input, example));
 
-    cmd.wait().unwrap();
+    let mut test_args = vec!["test".to_string(), "--bin".to_string(), bin_name(year, day)];
+    if release {
+        test_args.push("--release".to_string());
+    }
+
+    loop {
+        // files saved while the day runs trigger the next run.
+        let snapshot = get_snapshot(year, day, input_path.as_deref());
+
+        print!("{ANSI_CLEAR}");
+        run_watched(&run_args);
+        if test {
+            println!();
+            run_watched(&test_args);
+        }
+
+        println!(
+            "\n{ANSI_ITALIC}Watching {} file(s) for changes. Press Ctrl-C to stop.{ANSI_RESET}",
+            snapshot.len()
+        );
+
+        wait_for_change(&snapshot, || get_snapshot(year, day, input_path.as_deref()));
+    }
+}
+
+fn run_watched(cmd_args: &[String]) {
+    match Command::new("cargo").args(cmd_args).status() {
+        Ok(status) if !status.success() => {
+            eprintln!("\n`cargo {}` failed: {status}", cmd_args[0]);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("\nFailed to run cargo: {e}"),
+    }
+}
+
+/// Modification times of the watched files of a day: the solution, the puzzle input (or the `--input` file) and
+/// all examples, including split ones. Files that do not exist yet are tracked too, so that creating them counts
+/// as a change.
+fn get_snapshot(
+    year: Year,
+    day: Day,
+    input: Option<&Path>,
+) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    let mut paths = vec![
+        PathBuf::from("src/bin").join(format!("{}.rs", bin_name(year, day))),
+        input.map_or_else(
+            || data_dir(year).join("inputs").join(format!("{day}.txt")),
+            Path::to_path_buf,
+        ),
+        data_dir(year).join("examples").join(format!("{day}.txt")),
+    ];
+
+    let split_prefix = format!("{day}-");
+    if let Ok(entries) = fs::read_dir(data_dir(year).join("examples")) {
+        paths.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            (name.starts_with(&split_prefix) && name.ends_with(".txt")).then_some(path)
+        }));
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Block until the snapshot differs from `previous` and has settled for the debounce time.
+fn wait_for_change<T: PartialEq>(previous: &T, get_snapshot: impl Fn() -> T) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut current = get_snapshot();
+        if current == *previous {
+            continue;
+        }
+
+        loop {
+            thread::sleep(DEBOUNCE);
+            let next = get_snapshot();
+            if next == current {
+                return;
+            }
+            current = next;
+        }
+    }
 }
@@ -1,11 +1,32 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
+use crate::template::benchmark_chart::Scale;
+use crate::template::compare::{self, Change, CompareConfig};
+use crate::template::history::{self, HistoryEntry, Revision};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, readme_benchmarks, runner::BenchConfig, Day, Year, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Stored timings of a year. Exits if they cannot be read, as storing new timings would overwrite them.
+fn read_stored_timings(year: Year) -> Timings {
+    Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read stored timings: {e}");
+        eprintln!("Fix or remove the file to continue.");
+        process::exit(1);
+    })
+}
+
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    chart_scale: Scale,
+    bench: BenchConfig,
+) {
+    let stored_timings = read_stored_timings(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,14 +42,26 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(year, &days_to_run, true, Some(bench), 1, None);
+    let timings = run.timings.unwrap();
 
     if store {
+        let revision = Revision::current();
+        let entries: Vec<HistoryEntry> = run
+            .days
+            .iter()
+            .filter_map(|d| HistoryEntry::from_reports(&revision, d.day, &d.reports))
+            .collect();
+
+        if let Err(e) = history::append(year, &entries) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, chart_scale) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -38,3 +71,74 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Print the stored benchmarks of a day across commits.
+pub fn show_history(year: Year, day: Day) {
+    let entries = history::read(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history: {e}");
+        process::exit(1);
+    });
+
+    if entries.is_empty() {
+        println!("No stored benchmarks for day {day}. Run `cargo time {day} --store` first.");
+        return;
+    }
+
+    println!("{ANSI_BOLD}Day {day} history{ANSI_RESET}");
+    println!("--------------");
+    println!("{}", history::format_trend(&entries));
+}
+
+/// Bench the selected days and compare them against the stored timings.
+/// Without a day, all days with stored timings are compared. Exits with a non-zero status if any part regressed.
+pub fn compare(year: Year, day: Option<Day>, bench: BenchConfig, config: CompareConfig) {
+    let mut baseline = read_stored_timings(year);
+
+    let days_to_run: HashSet<Day> = match day {
+        Some(day) => HashSet::from([day]),
+        None => baseline.data.iter().map(|t| t.day).collect(),
+    };
+    // days that are not benched now must not count as regressed.
+    baseline.data.retain(|t| days_to_run.contains(&t.day));
+
+    if days_to_run.is_empty() {
+        eprintln!("No stored timings to compare against. Run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let timings = run_multi(year, &days_to_run, true, Some(bench), 1, None)
+        .timings
+        .unwrap();
+
+    let comparisons = compare::compare(&baseline, &timings, &config);
+
+    println!(
+        "\n{ANSI_BOLD}Comparison{ANSI_RESET} (tolerance {:.1}%, significance {})",
+        config.tolerance * 100.0,
+        config.significance
+    );
+    println!("----------");
+    println!("{}", compare::format_table(&comparisons));
+    println!();
+
+    if comparisons
+        .iter()
+        .any(compare::PartComparison::is_without_stats)
+    {
+        eprintln!("Warning: parts marked `no stats` were compared by their mean only, as their baseline was stored without stats.");
+        eprintln!("Run `cargo time --all --store` to store a baseline that can be tested for significance.");
+        eprintln!();
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.change == Change::Regressed)
+        .count();
+
+    if regressions > 0 {
+        eprintln!("{regressions} part(s) regressed.");
+        process::exit(1);
+    }
+
+    println!("No regressions.");
+}
@@ -0,0 +1,57 @@
+use std::process;
+
+use crate::template::{
+    all_days,
+    answers::{Answers, Check},
+    run_multi::run_multi,
+    Year, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(year: Year, is_release: bool) {
+    let run = run_multi(year, &all_days().collect(), is_release, None, 1, None);
+
+    let mut regressions = 0;
+    let mut verified = 0;
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    for day_run in &run.days {
+        let answers = match Answers::read(year, day_run.day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Day {}: failed to read accepted answers: {e}", day_run.day);
+                regressions += 1;
+                continue;
+            }
+        };
+
+        for check in answers.verify(day_run) {
+            match check {
+                Check::Verified { part } => {
+                    verified += 1;
+                    println!("Day {} Part {part}: ✓", day_run.day);
+                }
+                Check::Regressed { part, reason } => {
+                    regressions += 1;
+                    match part {
+                        Some(part) => println!("Day {} Part {part}: ✗ {reason}", day_run.day),
+                        None => println!("Day {}: ✗ {reason}", day_run.day),
+                    }
+                }
+                Check::Unverified { part } => {
+                    println!("Day {} Part {part}: no accepted answer", day_run.day);
+                }
+            }
+        }
+    }
+
+    println!();
+
+    if regressions > 0 {
+        eprintln!("{regressions} regression(s), {verified} answer(s) verified.");
+        process::exit(1);
+    }
+
+    println!("All {verified} accepted answer(s) verified.");
+}
@@ -0,0 +1,362 @@
+/// Compares fresh benchmarks against the stored baseline to detect performance regressions.
+use std::time::Duration;
+
+use crate::template::{
+    render_table,
+    report::PARSE_PART,
+    stats::{welch_p_value, BenchStats},
+    timings::{PartTiming, Timing, Timings},
+    Day,
+};
+
+/// Thresholds that decide whether a part changed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompareConfig {
+    /// Relative change of the mean that is tolerated, e.g. `0.05` for 5%.
+    pub tolerance: f64,
+    /// Maximum p-value of Welch's t-test for a change to count as significant.
+    pub significance: f64,
+}
+
+impl Default for CompareConfig {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.05,
+            significance: 0.05,
+        }
+    }
+}
+
+impl CompareConfig {
+    /// Replace the values that are present and validate the result.
+    pub fn with_overrides(
+        self,
+        tolerance: Option<f64>,
+        significance: Option<f64>,
+    ) -> Result<Self, String> {
+        let config = Self {
+            tolerance: tolerance.unwrap_or(self.tolerance),
+            significance: significance.unwrap_or(self.significance),
+        };
+
+        if config.tolerance.is_nan() || config.tolerance < 0.0 {
+            return Err("tolerance must not be negative.".into());
+        }
+
+        if !(config.significance > 0.0 && config.significance <= 1.0) {
+            return Err("significance level must be in range (0, 1].".into());
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parse a tolerance such as `5%` or `5` (percent) into a fraction.
+pub fn parse_tolerance(s: &str) -> Result<f64, String> {
+    s.trim()
+        .trim_end_matches('%')
+        .trim()
+        .parse::<f64>()
+        .map(|x| x / 100.0)
+        .map_err(|_| format!("invalid tolerance `{s}`, expecting a percentage such as `5%`."))
+}
+
+/// Outcome of comparing a part against its baseline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Significantly slower than the baseline, beyond the tolerance, or no longer producing a result.
+    Regressed,
+    /// Significantly faster than the baseline, beyond the tolerance.
+    Improved,
+    Unchanged,
+    /// The baseline has no timing for this part.
+    Missing,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Option<BenchStats>,
+    /// Mean of the baseline. Baselines migrated from timings without stats only have a mean.
+    pub baseline_mean: Option<Duration>,
+    pub current: Option<BenchStats>,
+    pub p_value: Option<f64>,
+    pub change: Change,
+}
+
+impl PartComparison {
+    /// Relative change of the mean, e.g. `0.1` if the current run is 10% slower.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn delta(&self) -> Option<f64> {
+        let baseline = self.baseline_mean?.as_nanos() as f64;
+        let current = self.current?.mean.as_nanos() as f64;
+        (baseline > 0.0).then(|| (current - baseline) / baseline)
+    }
+
+    /// Whether the baseline has a mean but no stats, so the change was not tested for significance.
+    pub fn is_without_stats(&self) -> bool {
+        self.baseline.is_none() && self.baseline_mean.is_some()
+    }
+}
+
+/// Compare every part of the `current` timings with the `baseline`.
+/// A part of the baseline that failed or is missing in the current run, e.g. because its day panicked or timed out,
+/// counts as regressed. A baseline without stats is compared by its mean alone, without a significance test.
+pub fn compare(
+    baseline: &Timings,
+    current: &Timings,
+    config: &CompareConfig,
+) -> Vec<PartComparison> {
+    let mut comparisons = vec![];
+
+    let mut days: Vec<Day> = baseline
+        .data
+        .iter()
+        .chain(&current.data)
+        .map(|t| t.day)
+        .collect();
+    days.sort();
+    days.dedup();
+
+    for day in days {
+        let stored = baseline.data.iter().find(|t| t.day == day);
+        let timing = current.data.iter().find(|t| t.day == day);
+
+        for part in [PARSE_PART, 1, 2] {
+            let current = timing.and_then(|t| get_part(t, part)).and_then(|p| p.stats);
+            let stored_part = stored.and_then(|t| get_part(t, part));
+            let baseline = stored_part.and_then(|p| p.stats);
+            let baseline_mean =
+                stored_part.map(|p| baseline.map_or_else(|| p.duration(), |s| s.mean));
+
+            // skip parts that were neither benched now nor before, e.g. the parse step of most solutions.
+            if current.is_none() && baseline_mean.is_none() {
+                continue;
+            }
+
+            let mut comparison = PartComparison {
+                day,
+                part,
+                baseline,
+                baseline_mean,
+                current,
+                p_value: baseline
+                    .zip(current)
+                    .and_then(|(b, c)| welch_p_value(&b, &c)),
+                change: Change::Missing,
+            };
+
+            if baseline_mean.is_some() && current.is_none() {
+                comparison.change = Change::Regressed;
+            } else if let Some(delta) = comparison.delta() {
+                let is_significant = comparison.p_value.is_none_or(|p| p <= config.significance);
+
+                comparison.change = match delta {
+                    d if d > config.tolerance && is_significant => Change::Regressed,
+                    d if d < -config.tolerance && is_significant => Change::Improved,
+                    _ => Change::Unchanged,
+                };
+            }
+
+            comparisons.push(comparison);
+        }
+    }
+
+    comparisons
+}
+
+fn get_part(timing: &Timing, part: u8) -> Option<PartTiming> {
+    let part = match part {
+        PARSE_PART => timing.parse,
+        1 => timing.part_1,
+        2 => timing.part_2,
+        _ => None,
+    };
+
+    part.filter(|p| !p.failed)
+}
+
+/// Render comparisons as a table with one row per part.
+pub fn format_table(comparisons: &[PartComparison]) -> String {
+    let duration = |mean: Option<Duration>| mean.map_or_else(|| "-".into(), |m| format!("{m:.1?}"));
+
+    let mut rows: Vec<[String; 7]> = vec![[
+        "Day".into(),
+        "Part".into(),
+        "Baseline".into(),
+        "Current".into(),
+        "Delta".into(),
+        "p-value".into(),
+        "Result".into(),
+    ]];
+
+    for c in comparisons {
+        rows.push([
+            c.day.to_string(),
+            if c.part == PARSE_PART {
+                "Parse".into()
+            } else {
+                c.part.to_string()
+            },
+            duration(c.baseline_mean),
+            duration(c.current.map(|s| s.mean)),
+            c.delta()
+                .map_or_else(|| "-".into(), |d| format!("{:+.1}%", d * 100.0)),
+            match c.p_value {
+                Some(p) => format!("{p:.3}"),
+                None if c.is_without_stats() => "no stats".into(),
+                None => "-".into(),
+            },
+            match c.change {
+                Change::Regressed if c.current.is_none() => "✗ no result",
+                Change::Regressed => "✗ regressed",
+                Change::Improved => "✓ improved",
+                Change::Unchanged => "unchanged",
+                Change::Missing => "no baseline",
+            }
+            .into(),
+        ]);
+    }
+
+    render_table(&rows)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_table, parse_tolerance, Change, CompareConfig};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+    use std::time::Duration;
+
+    fn stats(mean: u64, std_dev: u64) -> BenchStats {
+        BenchStats {
+            samples: 100,
+            mean: Duration::from_nanos(mean),
+            min: Duration::from_nanos(mean.saturating_sub(std_dev)),
+            median: Duration::from_nanos(mean),
+            p95: Duration::from_nanos(mean + std_dev),
+            std_dev: Duration::from_nanos(std_dev),
+            outliers: 0,
+        }
+    }
+
+    fn part(stats: BenchStats) -> PartTiming {
+        PartTiming {
+            nanos: stats.mean.as_nanos(),
+            samples: stats.samples as u128,
+            stats: Some(stats),
+            failed: false,
+        }
+    }
+
+    fn timings(part_1: Option<BenchStats>, part_2: Option<BenchStats>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                part_1: part_1.map(part),
+                part_2: part_2.map(part),
+            }],
+        }
+    }
+
+    #[test]
+    fn detects_significant_changes() {
+        let baseline = timings(Some(stats(1000, 10)), Some(stats(1000, 10)));
+        let current = timings(Some(stats(1200, 10)), Some(stats(800, 10)));
+
+        let result = compare(&baseline, &current, &CompareConfig::default());
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].change, Change::Regressed);
+        assert_eq!(result[1].change, Change::Improved);
+    }
+
+    #[test]
+    fn tolerates_small_or_noisy_changes() {
+        let baseline = timings(Some(stats(1000, 10)), Some(stats(1000, 2000)));
+        let current = timings(Some(stats(1030, 10)), Some(stats(1500, 2000)));
+
+        let result = compare(&baseline, &current, &CompareConfig::default());
+        // 3% slower is within the tolerance, 50% slower with a huge variance is not significant.
+        assert_eq!(result[0].change, Change::Unchanged);
+        assert_eq!(result[1].change, Change::Unchanged);
+        assert!(result[1].p_value.unwrap() > 0.05);
+    }
+
+    #[test]
+    fn reports_missing_baselines() {
+        let baseline = timings(Some(stats(1000, 10)), None);
+        let current = timings(Some(stats(1000, 10)), Some(stats(1000, 10)));
+
+        let result = compare(&baseline, &current, &CompareConfig::default());
+        assert_eq!(result[1].change, Change::Missing);
+        assert_eq!(result[1].delta(), None);
+    }
+
+    #[test]
+    fn compares_baselines_without_stats() {
+        let without_stats = |p: PartTiming| PartTiming {
+            stats: None,
+            samples: 0,
+            ..p
+        };
+        let mut baseline = timings(Some(stats(1000, 10)), Some(stats(1000, 10)));
+        let timing = &mut baseline.data[0];
+        timing.part_1 = timing.part_1.map(without_stats);
+        timing.part_2 = timing.part_2.map(without_stats);
+        let current = timings(Some(stats(1200, 10)), Some(stats(1010, 10)));
+
+        let result = compare(&baseline, &current, &CompareConfig::default());
+        assert_eq!(result[0].change, Change::Regressed);
+        assert_eq!(result[1].change, Change::Unchanged);
+        assert!(result
+            .iter()
+            .all(|c| c.is_without_stats() && c.p_value.is_none()));
+        assert!(format_table(&result).contains("no stats"));
+    }
+
+    #[test]
+    fn regresses_missing_days() {
+        let baseline = timings(Some(stats(1000, 10)), Some(stats(1000, 10)));
+        let current = Timings { data: vec![] };
+
+        let result = compare(&baseline, &current, &CompareConfig::default());
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|c| c.change == Change::Regressed));
+        assert!(format_table(&result).contains("✗ no result"));
+    }
+
+    #[test]
+    fn regresses_failed_parts() {
+        let baseline = timings(Some(stats(1000, 10)), Some(stats(1000, 10)));
+        let mut current = timings(Some(stats(1000, 10)), Some(stats(1000, 10)));
+        current.data[0].part_2 = current.data[0]
+            .part_2
+            .map(|p| PartTiming { failed: true, ..p });
+
+        let result = compare(&baseline, &current, &CompareConfig::default());
+        assert_eq!(result[0].change, Change::Unchanged);
+        assert_eq!(result[1].change, Change::Regressed);
+    }
+
+    #[test]
+    fn parses_tolerances() {
+        assert_eq!(parse_tolerance("5%"), Ok(0.05));
+        assert_eq!(parse_tolerance("10"), Ok(0.1));
+        assert!(parse_tolerance("fast").is_err());
+        assert!(CompareConfig::default()
+            .with_overrides(Some(-0.1), None)
+            .is_err());
+        assert!(CompareConfig::default()
+            .with_overrides(None, Some(0.0))
+            .is_err());
+    }
+}
@@ -0,0 +1,240 @@
+/// Inputs of a day beyond the puzzle input, e.g. the personal inputs of a team.
+/// A corpus lives in `data/<year>/inputs/NN/*.txt`, with optional expected answers for an input `<name>.txt` stored
+/// next to it as `<name>.json`, in the format of the accepted answers.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::{
+    answers::{Answers, Verdict},
+    data_dir, render_table,
+    run_multi::{DayRun, DayStatus},
+    Day, Year,
+};
+
+/// Name of the row that holds the puzzle input.
+const PUZZLE_INPUT: &str = "(puzzle)";
+
+/// An input file together with the answers it is expected to produce.
+pub struct CorpusInput {
+    pub name: String,
+    pub path: PathBuf,
+    pub expected: Answers,
+}
+
+/// Outcome of running a solution against a single input.
+pub struct CorpusRun {
+    pub input: CorpusInput,
+    pub run: DayRun,
+}
+
+impl CorpusRun {
+    fn verdict(&self, part: u8) -> Verdict {
+        let answer = self
+            .run
+            .reports
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.answer.as_deref());
+
+        self.input.expected.check(part, answer)
+    }
+
+    /// Whether the run did not complete or an answer differs from the expected one.
+    pub fn is_disagreement(&self) -> bool {
+        let completed = matches!(self.run.status, DayStatus::Solved | DayStatus::Unsolved);
+        !completed
+            || [1, 2]
+                .iter()
+                .any(|&part| matches!(self.verdict(part), Verdict::Incorrect { .. }))
+    }
+}
+
+pub fn get_corpus_dir(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("inputs").join(day.to_string())
+}
+
+/// List the inputs of a day: the puzzle input first, if present, followed by the corpus sorted by file name.
+/// The puzzle input is expected to produce the accepted answers.
+pub fn read_inputs(year: Year, day: Day) -> Result<Vec<CorpusInput>, String> {
+    let mut inputs = vec![];
+
+    let puzzle_path = data_dir(year).join("inputs").join(format!("{day}.txt"));
+    if puzzle_path.exists() {
+        inputs.push(CorpusInput {
+            name: PUZZLE_INPUT.into(),
+            path: puzzle_path,
+            expected: Answers::read(year, day)?,
+        });
+    }
+
+    let mut paths: Vec<PathBuf> = match fs::read_dir(get_corpus_dir(year, day)) {
+        Ok(entries) => entries
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(e.to_string()),
+    };
+
+    paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort();
+
+    for path in paths {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let expected = Answers::read_path(&path.with_extension("json"))
+            .map_err(|e| format!("{name}.json: {e}"))?;
+
+        inputs.push(CorpusInput {
+            name,
+            path,
+            expected,
+        });
+    }
+
+    Ok(inputs)
+}
+
+/// Render the answers and timings of every input as a table with one row per input.
+pub fn format_matrix(runs: &[CorpusRun]) -> String {
+    let mut rows: Vec<[String; 6]> = vec![[
+        "Input".into(),
+        "Part 1".into(),
+        "Time".into(),
+        "Part 2".into(),
+        "Time".into(),
+        "Result".into(),
+    ]];
+
+    for corpus_run in runs {
+        let part = |part: u8| {
+            let Some(report) = corpus_run.run.reports.iter().find(|r| r.part == part) else {
+                return ["-".to_string(), "-".to_string()];
+            };
+
+            let answer = report.answer.as_deref().unwrap_or("✖");
+            let cell = match corpus_run.verdict(part) {
+                Verdict::Correct => format!("{answer} ✓"),
+                Verdict::Incorrect { expected } => format!("{answer} ✗ (expected {expected})"),
+                Verdict::Unknown => answer.to_string(),
+            };
+
+            [cell, format!("{:.1?}", report.duration())]
+        };
+
+        let [part_1, time_1] = part(1);
+        let [part_2, time_2] = part(2);
+
+        let result = match &corpus_run.run.status {
+            _ if !corpus_run.is_disagreement() => "ok".to_string(),
+            DayStatus::Solved | DayStatus::Unsolved => "✗ disagrees".to_string(),
+            DayStatus::Panicked(message) => {
+                format!("✗ panicked: {}", message.as_deref().unwrap_or("no message"))
+            }
+            DayStatus::TimedOut(timeout) => format!("✗ timed out after {timeout:?}"),
+            DayStatus::Failed(reason) => format!("✗ failed: {reason}"),
+            DayStatus::Missing => "✗ not scaffolded".to_string(),
+        };
+
+        rows.push([
+            corpus_run.input.name.clone(),
+            part_1,
+            time_1,
+            part_2,
+            time_2,
+            result,
+        ]);
+    }
+
+    render_table(&rows)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_matrix, CorpusInput, CorpusRun};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            report::{PartReport, PartStatus},
+            run_multi::{DayRun, DayStatus},
+        },
+    };
+
+    fn report(part: u8, answer: &str, duration_nanos: u128) -> PartReport {
+        PartReport {
+            part,
+            answer: Some(answer.into()),
+            duration_nanos,
+            samples: 1,
+            stats: None,
+            status: PartStatus::Solved,
+        }
+    }
+
+    fn corpus_run(
+        name: &str,
+        expected: (Option<&str>, Option<&str>),
+        reports: Vec<PartReport>,
+        status: DayStatus,
+    ) -> CorpusRun {
+        CorpusRun {
+            input: CorpusInput {
+                name: name.into(),
+                path: format!("{name}.txt").into(),
+                expected: Answers {
+                    part_1: expected.0.map(Into::into),
+                    part_2: expected.1.map(Into::into),
+                },
+            },
+            run: DayRun {
+                day: day!(1),
+                reports,
+                status,
+            },
+        }
+    }
+
+    #[test]
+    fn flags_disagreements() {
+        let runs = [
+            corpus_run(
+                "alice",
+                (Some("11"), Some("31")),
+                vec![report(1, "11", 9_300), report(2, "31", 18_800)],
+                DayStatus::Solved,
+            ),
+            corpus_run(
+                "bob",
+                (Some("12"), None),
+                vec![report(1, "13", 1_200_000), report(2, "7", 1_500_000)],
+                DayStatus::Solved,
+            ),
+            corpus_run(
+                "carol",
+                (None, None),
+                vec![report(1, "5", 100)],
+                DayStatus::Panicked(Some("overflow".into())),
+            ),
+        ];
+
+        assert!(!runs[0].is_disagreement());
+        assert!(runs[1].is_disagreement());
+        assert!(runs[2].is_disagreement());
+
+        assert_eq!(
+            format_matrix(&runs),
+            [
+                "Input  Part 1              Time     Part 2  Time    Result",
+                "alice  11 ✓                9.3µs    31 ✓    18.8µs  ok",
+                "bob    13 ✗ (expected 12)  1.2ms    7       1.5ms   ✗ disagrees",
+                "carol  5                   100.0ns  -       -       ✗ panicked: overflow",
+            ]
+            .join("\n")
+        );
+    }
+}
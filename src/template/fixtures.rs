@@ -0,0 +1,48 @@
+/// Fixtures shared by the tests of the template modules.
+use crate::{
+    day,
+    template::{
+        timings::{PartTiming, Timing, Timings},
+        Year,
+    },
+};
+
+pub(crate) fn year() -> Year {
+    Year::new(2024).unwrap()
+}
+
+/// Timing of a part that took `nanos` in a single sample.
+pub(crate) fn part(nanos: u128) -> Option<PartTiming> {
+    Some(PartTiming {
+        nanos,
+        samples: 1,
+        stats: None,
+        failed: false,
+    })
+}
+
+/// Timings of days 1, 2 and 4, with parts between 10ms and 50ms.
+pub(crate) fn get_mock_timings() -> Timings {
+    Timings {
+        data: vec![
+            Timing {
+                day: day!(1),
+                parse: None,
+                part_1: part(10_000_000),
+                part_2: part(20_000_000),
+            },
+            Timing {
+                day: day!(2),
+                parse: None,
+                part_1: part(30_000_000),
+                part_2: part(40_000_000),
+            },
+            Timing {
+                day: day!(4),
+                parse: None,
+                part_1: part(40_000_000),
+                part_2: part(50_000_000),
+            },
+        ],
+    }
+}
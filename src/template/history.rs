@@ -0,0 +1,410 @@
+/// Append-only log of stored benchmarks, used to follow the performance of a day across commits.
+/// Every `cargo time --store` appends one JSON record per benched day to `data/<year>/history.jsonl`.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    data_dir, render_table,
+    report::{PartReport, PartStatus, PARSE_PART},
+    Day, Year,
+};
+
+/// State of the git checkout at the time of a benchmark.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Revision {
+    /// Abbreviated hash of `HEAD`, [`None`] outside of a git repository.
+    pub commit: Option<String>,
+    /// Set if tracked files had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Revision {
+    /// Read the current commit and dirty flag from `git`, timestamped with the current time.
+    pub fn current() -> Self {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        };
+
+        let commit = git(&["rev-parse", "--short", "HEAD"]).filter(|x| !x.is_empty());
+        let dirty = commit.is_some()
+            && git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|x| !x.is_empty());
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            commit,
+            dirty,
+            timestamp,
+        }
+    }
+}
+
+/// Benchmark of a single day at a revision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub revision: Revision,
+    pub day: Day,
+    pub parse_nanos: Option<u128>,
+    pub part_1_nanos: Option<u128>,
+    pub part_2_nanos: Option<u128>,
+}
+
+impl HistoryEntry {
+    /// Build an entry from the part reports of a day. Returns [`None`] if no part was solved.
+    pub fn from_reports(revision: &Revision, day: Day, reports: &[PartReport]) -> Option<Self> {
+        let nanos = |part: u8| {
+            reports
+                .iter()
+                .find(|r| r.part == part && r.status == PartStatus::Solved)
+                .map(|r| r.duration_nanos)
+        };
+
+        let entry = Self {
+            revision: revision.clone(),
+            day,
+            parse_nanos: nanos(PARSE_PART),
+            part_1_nanos: nanos(1),
+            part_2_nanos: nanos(2),
+        };
+
+        (entry.part_1_nanos.is_some() || entry.part_2_nanos.is_some()).then_some(entry)
+    }
+}
+
+/// Append entries to the history of a year.
+pub fn append(year: Year, entries: &[HistoryEntry]) -> io::Result<()> {
+    let path = get_history_path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    for entry in entries {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Read the history of a day, oldest entry first. A missing history yields no entries.
+pub fn read(year: Year, day: Day) -> Result<Vec<HistoryEntry>, String> {
+    let contents = match fs::read_to_string(get_history_path(year)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    Ok(parse_history(&contents)?
+        .into_iter()
+        .filter(|e| e.day == day)
+        .collect())
+}
+
+fn parse_history(contents: &str) -> Result<Vec<HistoryEntry>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("History line is not valid JSON."))?;
+            HistoryEntry::try_from(&json)
+        })
+        .collect()
+}
+
+fn get_history_path(year: Year) -> PathBuf {
+    data_dir(year).join("history.jsonl")
+}
+
+/// Render the history of a day as a table, showing the change of every part relative to the previous entry.
+pub fn format_trend(entries: &[HistoryEntry]) -> String {
+    let has_parse = entries.iter().any(|e| e.parse_nanos.is_some());
+
+    let mut header = vec!["Date", "Commit"];
+    if has_parse {
+        header.push("Parse");
+    }
+    header.extend(["Part 1", "Part 2"]);
+
+    let mut rows: Vec<Vec<String>> = vec![header.into_iter().map(String::from).collect()];
+
+    let mut previous: [Option<u128>; 3] = [None; 3];
+
+    for entry in entries {
+        let commit = match &entry.revision.commit {
+            Some(commit) if entry.revision.dirty => format!("{commit}*"),
+            Some(commit) => commit.clone(),
+            None => "-".into(),
+        };
+
+        let mut row = vec![format_timestamp(entry.revision.timestamp), commit];
+
+        let parts = [entry.parse_nanos, entry.part_1_nanos, entry.part_2_nanos];
+
+        for (i, nanos) in parts.into_iter().enumerate() {
+            if i == 0 && !has_parse {
+                continue;
+            }
+            row.push(format_change(nanos, previous[i]));
+            if nanos.is_some() {
+                previous[i] = nanos;
+            }
+        }
+
+        rows.push(row);
+    }
+
+    render_table(&rows)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn format_change(nanos: Option<u128>, previous: Option<u128>) -> String {
+    let Some(nanos) = nanos else {
+        return "-".into();
+    };
+
+    let duration = format!("{:.1?}", Duration::from_nanos(nanos as u64));
+
+    match previous {
+        Some(previous) if previous > 0 => {
+            let change = (nanos as f64 - previous as f64) / previous as f64 * 100.0;
+            format!("{duration} ({change:+.1}%)")
+        }
+        _ => duration,
+    }
+}
+
+/// Format a unix timestamp as UTC date and time, e.g. `2024-12-05 14:03`.
+#[allow(clippy::cast_possible_wrap)]
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let nanos = |x: Option<u128>| x.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64));
+
+        map.insert(
+            "commit".into(),
+            value
+                .revision
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.revision.dirty));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.revision.timestamp as f64),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("parse_nanos".into(), nanos(value.parse_nanos));
+        map.insert("part_1_nanos".into(), nanos(value.part_1_nanos));
+        map.insert("part_2_nanos".into(), nanos(value.part_2_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let commit = match json.get("commit") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected history.commit to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected history.dirty to be a boolean.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| v.parse::<Day>().ok())
+            .ok_or("Expected history.day to be a day string.")?;
+
+        let nanos = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| Some(*v as u128))
+                .ok_or(format!("Expected history.{key} to be null or number.")),
+        };
+
+        Ok(HistoryEntry {
+            revision: Revision {
+                commit,
+                dirty,
+                timestamp,
+            },
+            day,
+            parse_nanos: nanos("parse_nanos")?,
+            part_1_nanos: nanos("part_1_nanos")?,
+            part_2_nanos: nanos("part_2_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, format_trend, parse_history, HistoryEntry, Revision};
+    use crate::{
+        day,
+        template::report::{PartReport, PartStatus},
+    };
+    use tinyjson::JsonValue;
+
+    fn entry(commit: &str, dirty: bool, part_1: u128, part_2: Option<u128>) -> HistoryEntry {
+        HistoryEntry {
+            revision: Revision {
+                commit: Some(commit.into()),
+                dirty,
+                timestamp: 1_733_407_380,
+            },
+            day: day!(5),
+            parse_nanos: None,
+            part_1_nanos: Some(part_1),
+            part_2_nanos: part_2,
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let mut entries = vec![entry("abc1234", true, 1_000, Some(2_000))];
+        entries.push(HistoryEntry {
+            revision: Revision::default(),
+            parse_nanos: Some(10),
+            ..entry("-", false, 1, None)
+        });
+
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|e| JsonValue::from(e).stringify().unwrap())
+            .collect();
+
+        assert_eq!(parse_history(&lines.join("\n")).unwrap(), entries);
+    }
+
+    #[test]
+    fn builds_entries_from_solved_parts() {
+        let report = |part: u8, status: PartStatus| PartReport {
+            part,
+            answer: None,
+            duration_nanos: 42,
+            samples: 10,
+            stats: None,
+            status,
+        };
+
+        let revision = Revision::default();
+
+        let entry = HistoryEntry::from_reports(
+            &revision,
+            day!(1),
+            &[
+                report(1, PartStatus::Solved),
+                report(2, PartStatus::Unsolved),
+            ],
+        )
+        .unwrap();
+        assert_eq!(entry.part_1_nanos, Some(42));
+        assert_eq!(entry.part_2_nanos, None);
+
+        assert!(
+            HistoryEntry::from_reports(&revision, day!(1), &[report(1, PartStatus::Unsolved)])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_407_380), "2024-12-05 14:03");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn formats_trends() {
+        let trend = format_trend(&[
+            entry("abc1234", false, 2_000_000, None),
+            entry("def5678", true, 1_500_000, Some(100)),
+        ]);
+
+        assert_eq!(
+            trend,
+            [
+                "Date              Commit    Part 1          Part 2",
+                "2024-12-05 14:03  abc1234   2.0ms           -",
+                "2024-12-05 14:03  def5678*  1.5ms (-25.0%)  100.0ns",
+            ]
+            .join("\n")
+        );
+    }
+}
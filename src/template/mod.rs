@@ -1,44 +1,150 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod benchmark_chart;
 pub mod commands;
+pub mod compare;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
+mod corpus;
 mod day;
+mod history;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod stats;
+mod submissions;
+mod templates;
 mod timings;
+mod year;
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod fixtures;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Directory that holds the data files of a year, e.g. `data/2024`.
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join(year.to_string())
+}
+
+/// Data files of the layout before data was split by year, e.g. `data/inputs` instead of `data/2024/inputs`.
+const LEGACY_DATA: [&str; 6] = [
+    "inputs",
+    "examples",
+    "puzzles",
+    "answers",
+    "submissions",
+    "timings.json",
+];
+
+/// Warn about data files in the layout before data was split by year, as they are no longer read.
+pub fn warn_legacy_data(year: Year) {
+    let data = env::current_dir().unwrap().join("data");
+
+    let has_files = |name: &&str| {
+        let path = data.join(name);
+        path.is_file()
+            || fs::read_dir(path).is_ok_and(|mut entries| {
+                entries.any(|entry| entry.is_ok_and(|entry| entry.file_name() != ".keep"))
+            })
+    };
+
+    let legacy: Vec<&str> = LEGACY_DATA.iter().copied().filter(has_files).collect();
+    if legacy.is_empty() {
+        return;
+    }
+
+    eprintln!("Warning: found data files in `data/` that are no longer read, as data now lives in `data/<year>/`.");
+    eprintln!("Move them to keep using them for {year}:");
+    for name in legacy {
+        if name.ends_with(".json") {
+            eprintln!("  mv data/{name} data/{year}/{name}");
+        } else {
+            eprintln!("  mkdir -p data/{year}/{name} && mv data/{name}/* data/{year}/{name}/");
+        }
+    }
+    eprintln!();
+}
+
+/// Name of the binary that holds the solution for a day, e.g. `2024-01`.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    let filepath = data_dir(year).join(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    let filepath = data_dir(year)
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Render rows of cells as a plain-text table, padding every column to its widest cell.
+/// The first row is the header, trailing whitespace is trimmed from every line.
+pub(crate) fn render_table<R: AsRef<[String]>>(rows: &[R]) -> String {
+    let columns = rows.first().map_or(0, |row| row.as_ref().len());
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row.as_ref()[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            row.as_ref()
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+/// The year is read from the file name of the solution, e.g. `src/bin/2024-01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = <fn>`, the input is parsed once by the provided function and a reference to the parsed
+/// value is passed to both parts. The parse step is timed separately from the parts:
+///
+/// ```ignore
+/// advent_of_code::solution!(1, parse = Input::from_str);
+///
+/// pub fn part_one(input: &Input) -> Option<u32> { ... }
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +156,65 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn __run() {
+            use $crate::template::runner::*;
+            let input = read_input(YEAR, DAY);
+            $( run_part($func, input.part($part), YEAR, DAY, $part); )*
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn __run() {
+            use $crate::template::runner::*;
+            let input = read_input(YEAR, DAY);
+            // split examples are parsed once per part.
+            let parsed = input.shared().map(|input| run_parse($parse, input));
+            $(
+                match &parsed {
+                    Some(parsed) => run_part($func, parsed, YEAR, DAY, $part),
+                    None => run_part($func, &run_parse($parse, input.part($part)), YEAR, DAY, $part),
+                }
+            )*
+        }
+    };
+
+    (@header $day:expr) => {
+        /// The year of the current solution.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_bin_path(file!());
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
+        /// Registry entry of this solution, used when it is linked into the main binary.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution { year: YEAR, day: DAY, run: __run };
+
+        // NOTE: with the `registry` feature, all solutions are linked into one binary, which can only have one allocator.
+        #[cfg(all(feature = "dhat-heap", not(feature = "registry")))]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // NOTE: unused when the solution is linked into the main binary.
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::runner::report_panics();
+            __run();
+            $crate::template::runner::finish();
         }
     };
 }
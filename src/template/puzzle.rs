@@ -0,0 +1,248 @@
+/// Extracts the example input and the example answers from a downloaded puzzle description.
+/// The description is the HTML of the puzzle's `<article>` elements, one per part, as written by `cargo download`.
+use crate::template::aoc_client::{extract_elements, html_to_text, strip_tags};
+
+/// Split the description into parts. Every part starts with a `<h2>` heading.
+fn split_parts(puzzle: &str) -> Vec<&str> {
+    let mut starts: Vec<usize> = puzzle.match_indices("<h2").map(|(i, _)| i).collect();
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain([&puzzle.len()]))
+        .map(|(&start, &end)| &puzzle[start..end])
+        .filter(|part| !part.trim().is_empty())
+        .collect()
+}
+
+/// Inner HTML of all code blocks (`<pre><code>`) of a part.
+fn code_blocks(html: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        let content_start = start + "<pre><code>".len();
+        let Some(end) = rest[content_start..].find("</code></pre>") else {
+            break;
+        };
+        blocks.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end..];
+    }
+
+    blocks
+}
+
+/// The title of the puzzle, e.g. `Historian Hysteria` for a heading `--- Day 1: Historian Hysteria ---`.
+pub fn extract_title(puzzle: &str) -> Option<String> {
+    let heading = extract_elements(puzzle, "h2").into_iter().next()?;
+    let heading = html_to_text(&heading);
+    let heading = heading.trim_start_matches('-').trim_end_matches('-').trim();
+
+    let title = heading
+        .split_once(':')
+        .map_or(heading, |(_, title)| title)
+        .trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// The first code block of the puzzle, which by convention holds the example input.
+pub fn extract_example(puzzle: &str) -> Option<String> {
+    code_blocks(puzzle)
+        .first()
+        .and_then(|block| to_example(block))
+}
+
+/// The example of a part, for days with split examples: the first code block of the part.
+/// Part two falls back to the example of part one if it does not introduce its own.
+pub fn extract_part_example(puzzle: &str, part: u8) -> Option<String> {
+    let parts = split_parts(puzzle);
+    let html = parts.get(usize::from(part).saturating_sub(1))?;
+
+    match code_blocks(html).first() {
+        Some(block) => to_example(block),
+        None if part == 2 => extract_part_example(puzzle, 1),
+        None => None,
+    }
+}
+
+fn to_example(block: &str) -> Option<String> {
+    let mut example = strip_tags(block);
+
+    if example.trim().is_empty() {
+        return None;
+    }
+    if !example.ends_with('\n') {
+        example.push('\n');
+    }
+
+    Some(example)
+}
+
+/// The answer of a part for the example input, i.e. the last emphasized code (`<code><em>`) of the part.
+/// Fails with a reason if the part has not been downloaded yet, or if the answer cannot be attributed to the
+/// example input with confidence: when a part shows more than one example, or, unless examples are `split`, part two
+/// introduces its own.
+pub fn extract_answer(puzzle: &str, part: u8, split: bool) -> Result<String, String> {
+    let parts = split_parts(puzzle);
+    let Some(html) = parts.get(usize::from(part).saturating_sub(1)) else {
+        return Err("the part has not been downloaded".into());
+    };
+
+    let blocks = code_blocks(html).len();
+    if blocks > 1 || (part == 2 && !split && blocks > 0) {
+        return Err("the part has more than one example".into());
+    }
+
+    let answer = html
+        .match_indices("<code><em>")
+        .filter_map(|(start, tag)| {
+            let content = &html[start + tag.len()..];
+            content.find("</em></code>").map(|end| &content[..end])
+        })
+        .last()
+        .map(html_to_text)
+        .ok_or("the part has no emphasized answer")?;
+
+    let digits = answer.strip_prefix('-').unwrap_or(&answer);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("the emphasized answer `{answer}` is not a number"));
+    }
+
+    Ok(answer)
+}
+
+/// Replace the `None` placeholder in the example test of a part with its answer.
+/// Returns `None` if the module does not contain the placeholder, e.g. because it has been edited.
+pub fn fill_test_answer(module: &str, part: u8, answer: &str) -> Option<String> {
+    let function = if part == 1 { "part_one" } else { "part_two" };
+    let readers = [
+        "read_file(\"examples\", YEAR, DAY)".to_string(),
+        format!("read_file_part(\"examples\", YEAR, DAY, {part})"),
+    ];
+
+    readers.iter().find_map(|reader| {
+        let placeholder = format!(
+            "let result = {function}(&advent_of_code::template::{reader});\n        assert_eq!(result, None);"
+        );
+
+        module.contains(&placeholder).then(|| {
+            module.replacen(
+                &placeholder,
+                &placeholder.replace("None", &format!("Some({answer})")),
+                1,
+            )
+        })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        extract_answer, extract_example, extract_part_example, extract_title, fill_test_answer,
+    };
+    use crate::template::templates::split_example_tests;
+
+    const PUZZLE: &str = r#"<h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+2   5
+</code></pre>
+<p>In the example above, this is <code>2 + 1 + 0</code>, a <em>total distance</em> of <code><em>11</em></code>!</p>
+
+<h2 id="part2">--- Part Two ---</h2><p>So, for these example lists, the similarity score is <code><em>31</em></code> (<code>9 + 4</code>).</p>"#;
+
+    #[test]
+    fn extracts_title() {
+        assert_eq!(extract_title(PUZZLE), Some("Historian Hysteria".into()));
+        assert_eq!(
+            extract_title("<h2>--- Day 5: Print &amp; Queue ---</h2>"),
+            Some("Print & Queue".into())
+        );
+        assert_eq!(extract_title("<p>No heading.</p>"), None);
+    }
+
+    #[test]
+    fn extracts_example() {
+        assert_eq!(
+            extract_example(PUZZLE),
+            Some("3   4\n4   3\n2   5\n".into())
+        );
+        assert_eq!(
+            extract_example("<pre><code>  a &lt; <em>b</em></code></pre>"),
+            Some("  a < b\n".into())
+        );
+        assert_eq!(extract_example("<p>No example.</p>"), None);
+    }
+
+    #[test]
+    fn extracts_answers() {
+        assert_eq!(extract_answer(PUZZLE, 1, false), Ok("11".into()));
+        assert_eq!(extract_answer(PUZZLE, 2, false), Ok("31".into()));
+
+        let part_one = PUZZLE.split("\n\n").next().unwrap();
+        assert!(extract_answer(part_one, 2, false).is_err());
+    }
+
+    #[test]
+    fn rejects_ambiguous_answers() {
+        let two_examples = PUZZLE.replacen(
+            "<p>In the example",
+            "<pre><code>1\n</code></pre><p>In the example",
+            1,
+        );
+        assert!(extract_answer(&two_examples, 1, false).is_err());
+
+        let new_example = format!("{PUZZLE}<pre><code>xmul(2,4)\n</code></pre>");
+        assert_eq!(extract_answer(&new_example, 1, false), Ok("11".into()));
+        assert!(extract_answer(&new_example, 2, false).is_err());
+
+        let no_answer = "<h2>--- Day 2 ---</h2><p>A <em>safe</em> report.</p>";
+        assert!(extract_answer(no_answer, 1, false).is_err());
+
+        let text = "<h2>--- Day 3 ---</h2><p>The code is <code><em>4,6,3</em></code>.</p>";
+        assert!(extract_answer(text, 1, false).is_err());
+    }
+
+    #[test]
+    fn extracts_split_examples() {
+        let split = format!(
+            "{PUZZLE}<p>For example:</p><pre><code>xmul(2,4)\n</code></pre><p>This adds up to <code><em>48</em></code>.</p>"
+        );
+
+        assert_eq!(
+            extract_part_example(&split, 1),
+            Some("3   4\n4   3\n2   5\n".into())
+        );
+        assert_eq!(extract_part_example(&split, 2), Some("xmul(2,4)\n".into()));
+        assert_eq!(extract_answer(&split, 2, true), Ok("48".into()));
+
+        // part two without an example of its own reuses the one of part one.
+        assert_eq!(extract_part_example(PUZZLE, 2), extract_example(PUZZLE));
+        assert_eq!(extract_answer(PUZZLE, 2, true), Ok("31".into()));
+
+        let part_one = PUZZLE.split("\n\n").next().unwrap();
+        assert_eq!(extract_part_example(part_one, 2), None);
+    }
+
+    #[test]
+    fn fills_test_answers() {
+        let module = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+        let filled = fill_test_answer(module, 2, "31").unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(31));"));
+        assert_eq!(filled.matches("assert_eq!(result, None);").count(), 1);
+
+        let filled = fill_test_answer(&filled, 1, "11").unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(11));"));
+        assert_eq!(fill_test_answer(&filled, 1, "11"), None);
+        let split = split_example_tests(module).unwrap();
+        let filled = fill_test_answer(&split, 2, "48").unwrap();
+        assert!(filled.contains(
+            "part_two(&advent_of_code::template::read_file_part(\"examples\", YEAR, DAY, 2));\n        assert_eq!(result, Some(48));"
+        ));
+    }
+}
@@ -2,10 +2,13 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::benchmark_chart::{self, Scale};
+use crate::template::timings::{PartTiming, Timings};
+use crate::template::{bin_name, Day, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
+/// Marker of the single table that readmes had before tables were kept per year.
+static LEGACY_MARKER: &str = "<!--- benchmarking table --->";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -26,12 +29,49 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Every year has its own table, delimited by markers such as `<!--- benchmarking table 2024 --->`.
+fn get_marker(year: Year) -> String {
+    format!("{MARKER_PREFIX} {year} --->")
+}
+
+/// Turn the table of a readme from before tables were kept per year into the table of `year`.
+/// Returns `false` if the readme has no such table.
+fn adopt_legacy_markers(readme: &mut String, year: Year) -> bool {
+    if !readme.contains(LEGACY_MARKER) {
+        return false;
+    }
+
+    *readme = readme.replace(LEGACY_MARKER, &get_marker(year));
+    true
+}
+
+/// Add empty markers for a year that has no table yet, right after the last table of another year.
+fn insert_markers(readme: &mut String, marker: &str) -> Result<(), Error> {
+    let pos = readme
+        .match_indices(MARKER_PREFIX)
+        .filter_map(|(start, _)| {
+            if readme[start..].starts_with(LEGACY_MARKER) {
+                return Some(start + LEGACY_MARKER.len());
+            }
+
+            let (year, _) = readme[start + MARKER_PREFIX.len()..].split_once("--->")?;
+            year.parse::<Year>()
+                .ok()
+                .map(|year| start + get_marker(year).len())
+        })
+        .last()
+        .ok_or_else(|| Error::Parser("Could not find a benchmarking table.".into()))?;
+
+    readme.insert_str(pos, &format!("\n\n{marker}\n{marker}"));
+    Ok(())
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,132 +92,231 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_part(part: Option<PartTiming>) -> String {
+    match part {
+        None => "-".into(),
+        Some(p) if p.failed => "failed".into(),
+        Some(p) => format!("{:.1?}", p.duration()),
+    }
+}
+
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let marker = get_marker(year);
+    let header = format!("{prefix} {year} Benchmarks");
+
+    // only render a parse column if at least one solution times its parse step separately.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    if has_parse {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(year, timing.day);
+        let parse = if has_parse {
+            format!(" `{}` |", format_part(timing.parse))
+        } else {
+            String::new()
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format_part(timing.part_1),
+            format_part(timing.part_2)
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(String::new());
+    lines.push(format!(
+        "![{year} benchmark chart]({})",
+        benchmark_chart::get_readme_path(year)
+    ));
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let marker = get_marker(year);
+    if !s.contains(&marker) {
+        insert_markers(s, &marker)?;
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the table of a year in the readme and render the chart it references.
+pub fn update(year: Year, timings: Timings, scale: Scale) -> Result<(), Error> {
+    benchmark_chart::store(year, &timings, scale)?;
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+    // the table of a readme from before tables were kept per year belongs to the default year.
+    if Year::from_env() == Some(year)
+        && !readme.contains(&get_marker(year))
+        && adopt_legacy_markers(&mut readme, year)
+    {
+        println!("Converted the benchmarking table of the readme into the table of {year}.");
+    }
+
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
-
-    fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
-            ],
-        }
+    use super::{adopt_legacy_markers, get_marker, update_content, LEGACY_MARKER};
+    use crate::template::{
+        fixtures::{get_mock_timings, part, year},
+        timings::PartTiming,
+        Year,
+    };
+
+    fn marker() -> String {
+        get_marker(year())
     }
 
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("{} {} {}", marker(), marker(), marker());
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker(), marker());
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## 2024 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker(), marker());
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(&marker()).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(
+            s.matches("## 2024 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker(), marker());
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2024 --->",
+            "## 2024 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "",
+            "![2024 benchmark chart](./data/2024/benchmarks.svg)",
+            "<!--- benchmarking table 2024 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = part(5_000_000);
+
+        let mut s = format!("{}\n{}", marker(), marker());
+        update_content(&mut s, year(), timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024-01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"));
+    }
+
+    #[test]
+    fn format_failed_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2 = part(20_000_000).map(|p| PartTiming { failed: true, ..p });
+
+        let mut s = format!("{}\n{}", marker(), marker());
+        update_content(&mut s, year(), timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `failed` |"));
+    }
+
+    #[test]
+    fn adds_tables_for_new_years() {
+        let mut s = format!("foo\n{}\n{}\nbaz", marker(), marker());
+        update_content(&mut s, Year::new(2023).unwrap(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+
+        assert!(s.starts_with("foo\n<!--- benchmarking table 2024 --->\n## 2024 Benchmarks"));
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("![2023 benchmark chart](./data/2023/benchmarks.svg)"));
+        assert!(s.ends_with("<!--- benchmarking table 2023 --->\nbaz"));
+    }
+
+    #[test]
+    fn adopts_legacy_tables() {
+        let legacy = format!("foo\n{LEGACY_MARKER}\n## Benchmarks\n{LEGACY_MARKER}\nbaz");
+
+        let mut s = legacy.clone();
+        assert!(adopt_legacy_markers(&mut s, year()));
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+        assert!(!s.contains(LEGACY_MARKER));
+        assert_eq!(s.matches(&marker()).count(), 2);
+        assert!(s.starts_with("foo\n<!--- benchmarking table 2024 --->\n## 2024 Benchmarks"));
+        assert!(s.ends_with("<!--- benchmarking table 2024 --->\nbaz"));
+
+        let mut s = "# readme".to_string();
+        assert!(!adopt_legacy_markers(&mut s, year()));
+    }
+
+    #[test]
+    fn adds_tables_after_legacy_tables() {
+        let mut s = format!("foo\n{LEGACY_MARKER}\n## Benchmarks\n{LEGACY_MARKER}\nbaz");
+        update_content(&mut s, year(), get_mock_timings(), 190.0).unwrap();
+
+        assert_eq!(s.matches(LEGACY_MARKER).count(), 2);
+        assert!(s.contains(&format!(
+            "{LEGACY_MARKER}\n\n{}\n## 2024 Benchmarks",
+            marker()
+        )));
+        assert!(s.ends_with("<!--- benchmarking table 2024 --->\nbaz"));
+    }
 }
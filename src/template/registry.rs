@@ -0,0 +1,34 @@
+/// Registry of solutions that are linked into the running binary.
+/// When solutions are registered, `run_multi` executes them in-process instead of spawning `cargo run` per day.
+use std::sync::OnceLock;
+
+use crate::template::{Day, Year};
+
+/// A solution linked into the running binary. The `solution!` macro generates one per day as `SOLUTION`.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Reads the input and runs all parts of the solution.
+    pub run: fn(),
+}
+
+static REGISTRY: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Register the solutions linked into this binary. Only the first call has an effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = REGISTRY.set(solutions);
+}
+
+/// Returns `true` if solutions have been registered.
+pub fn is_active() -> bool {
+    REGISTRY.get().is_some()
+}
+
+/// Returns the registered solution for a day of a year.
+pub fn get(year: Year, day: Day) -> Option<&'static Solution> {
+    REGISTRY
+        .get()?
+        .iter()
+        .find(|s| s.year == year && s.day == day)
+}
@@ -0,0 +1,313 @@
+/// Machine-readable side channel between solution binaries and `run_multi`.
+///
+/// When the environment variable [`REPORT_FILE_ENV`] is set, the runner appends one JSON record per
+/// executed part to that file. The parent process reads these records back instead of scraping the
+/// human-readable `Part N: ...` lines from stdout.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::stats::{stats_from_json, stats_to_json, BenchStats};
+
+/// Name of the environment variable that holds the path of the report file.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// Part number used for the report of the parse step of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// Outcome of running a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error, with the messages of the error and its sources joined by `: `.
+    Failed(String),
+}
+
+impl PartStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed(_) => "failed",
+        }
+    }
+
+    fn from_json(status: &str, error: Option<&String>) -> Result<Self, String> {
+        match (status, error) {
+            ("solved", _) => Ok(PartStatus::Solved),
+            ("unsolved", _) => Ok(PartStatus::Unsolved),
+            ("failed", Some(error)) => Ok(PartStatus::Failed(error.clone())),
+            ("failed", None) => Err("Expected report.error to be a string.".into()),
+            (x, _) => Err(format!("Unknown part status `{x}`.")),
+        }
+    }
+}
+
+/// Record emitted by a solution binary for a single part, or for the parse step (see [`PARSE_PART`]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration_nanos: u128,
+    pub samples: u128,
+    /// Sample statistics, present if the part was benched.
+    pub stats: Option<BenchStats>,
+    pub status: PartStatus,
+}
+
+impl PartReport {
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation)]
+        Duration::from_nanos(self.duration_nanos as u64)
+    }
+
+    /// Append this record to the report file, if the parent process requested one.
+    pub fn emit(&self) -> io::Result<()> {
+        append_record(&JsonValue::from(self))
+    }
+}
+
+/// Records of a single run of a solution.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunReport {
+    pub parts: Vec<PartReport>,
+    /// Message of the panic that ended the run, if any.
+    pub panic: Option<String>,
+}
+
+/// Append a record with the message of a panic to the report file, if the parent process requested one.
+pub fn emit_panic(message: &str) -> io::Result<()> {
+    let record = HashMap::from([("panic".to_string(), JsonValue::String(message.into()))]);
+    append_record(&JsonValue::Object(record))
+}
+
+fn append_record(record: &JsonValue) -> io::Result<()> {
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = record
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read all records from a report file. A missing file yields no records.
+pub fn read_reports(path: &Path) -> Result<RunReport, String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(RunReport::default());
+    };
+    parse_reports(&contents)
+}
+
+/// Parse newline-delimited report records.
+pub fn parse_reports(contents: &str) -> Result<RunReport, String> {
+    let mut report = RunReport::default();
+
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        let json = JsonValue::from_str(line).or(Err("Report line is not valid JSON."))?;
+
+        let panic = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|map| map.get("panic"));
+
+        match panic {
+            Some(message) => {
+                report.panic = Some(
+                    message
+                        .get::<String>()
+                        .cloned()
+                        .ok_or("Expected report.panic to be a string.")?,
+                );
+            }
+            None => report.parts.push(PartReport::try_from(&json)?),
+        }
+    }
+
+    Ok(report)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos as f64),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        if let PartStatus::Failed(error) = &value.status {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected report.duration_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let stats = stats_from_json(json, "stats")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?;
+        let status =
+            PartStatus::from_json(status, json.get("error").and_then(|v| v.get::<String>()))?;
+
+        Ok(PartReport {
+            part,
+            answer: answer.cloned(),
+            duration_nanos,
+            samples,
+            stats,
+            status,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_reports, PartReport, PartStatus, RunReport};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = PartReport {
+            part: 2,
+            answer: Some("a (tricky) @ answer\nwith newlines".into()),
+            duration_nanos: 74_130_074,
+            samples: 99_999,
+            stats: None,
+            status: PartStatus::Solved,
+        };
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        let parsed = parse_reports(&format!("{line}\n")).unwrap();
+        assert_eq!(
+            parsed,
+            RunReport {
+                parts: vec![report],
+                panic: None
+            }
+        );
+    }
+
+    #[test]
+    fn parses_unsolved_reports() {
+        let parsed = parse_reports(
+            r#"{ "part": 1, "answer": null, "duration_nanos": 0, "samples": 1, "status": "unsolved" }"#,
+        )
+        .unwrap()
+        .parts;
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].answer, None);
+        assert_eq!(parsed[0].status, PartStatus::Unsolved);
+    }
+
+    #[test]
+    fn roundtrips_failed_reports() {
+        let report = PartReport {
+            part: 1,
+            answer: None,
+            duration_nanos: 1_200,
+            samples: 1,
+            stats: None,
+            status: PartStatus::Failed("bad line 3: invalid digit found in string".into()),
+        };
+
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(parse_reports(&line).unwrap().parts, vec![report]);
+
+        assert!(parse_reports(
+            r#"{ "part": 1, "answer": null, "duration_nanos": 0, "samples": 1, "status": "failed" }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parses_panics() {
+        let parsed = parse_reports(
+            r#"{ "part": 1, "answer": "1", "duration_nanos": 10, "samples": 1, "status": "solved" }
+{ "panic": "index out of bounds (src/bin/2024-01.rs:12:5)" }"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.parts.len(), 1);
+        assert_eq!(
+            parsed.panic.as_deref(),
+            Some("index out of bounds (src/bin/2024-01.rs:12:5)")
+        );
+    }
+
+    #[test]
+    fn handles_empty_reports() {
+        assert_eq!(parse_reports("").unwrap(), RunReport::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        parse_reports(r#"{ "part": 1 }"#).unwrap();
+    }
+}
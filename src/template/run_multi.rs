@@ -1,40 +1,182 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    bin_name, registry,
+    report::{PartReport, PartStatus, RunReport, PARSE_PART},
+    runner,
+    runner::BenchConfig,
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use child_commands::{OutputLine, RunOptions};
+
+/// How the solution of a day ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    /// Every part returned an answer.
+    Solved,
+    /// The solution ran to completion, but at least one part returned `None`.
+    Unsolved,
+    /// The solution panicked, with the panic message if it was reported.
+    Panicked(Option<String>),
+    /// The solution did not finish within the timeout and was killed.
+    TimedOut(Duration),
+    /// The solution could not be run or exited with an error other than a panic, e.g. a compile error.
+    Failed(String),
+    /// No solution has been scaffolded for the day.
+    Missing,
+}
 
-    let mut need_space = false;
+impl DayStatus {
+    /// Status of a solution that ran to completion or panicked, judged by its report.
+    fn from_report(report: &RunReport) -> Self {
+        if report.panic.is_some() {
+            return DayStatus::Panicked(report.panic.clone());
+        }
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        let failure = report.parts.iter().find_map(|r| match &r.status {
+            PartStatus::Failed(error) => Some(format!("part {}: {error}", r.part)),
+            _ => None,
+        });
+        if let Some(failure) = failure {
+            return DayStatus::Failed(failure);
+        }
+
+        let is_solved = !report.parts.is_empty()
+            && report
+                .parts
+                .iter()
+                .filter(|r| r.part != PARSE_PART)
+                .all(|r| r.status == PartStatus::Solved);
+
+        if is_solved {
+            DayStatus::Solved
+        } else {
+            DayStatus::Unsolved
+        }
+    }
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Part reports collected from the solution of a single day.
+pub struct DayRun {
+    pub day: Day,
+    pub reports: Vec<PartReport>,
+    pub status: DayStatus,
+}
+
+/// Outcome of running the solutions for a set of days.
+pub struct MultiRun {
+    pub days: Vec<DayRun>,
+    /// Timings of all days, present if the days were benched.
+    pub timings: Option<Timings>,
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+/// Run the solutions of `year` for `days_to_run`. Days are benched with `bench` if present.
+///
+/// With `jobs > 1`, up to `jobs` days run concurrently and the output of each day is printed as a group once it and all
+/// days before it are done. Benched runs and runs with the `registry` feature always execute one day at a time.
+///
+/// A solution bin that runs longer than `timeout` is killed. In-process solutions of the `registry` feature cannot be
+/// interrupted and ignore the timeout.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<BenchConfig>,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> MultiRun {
+    let mut collector = Collector {
+        days: Vec::with_capacity(days_to_run.len()),
+        timings: Vec::with_capacity(days_to_run.len()),
+        need_space: false,
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    // NOTE: use non-duplicate, sorted day values.
+    let days_to_run: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    if registry::is_active() {
+        for &day in &days_to_run {
+            collector.print_header(day);
+
+            let day_run = match registry::get(year, day) {
+                Some(solution) => {
+                    let report = runner::run_in_process(bench, solution.run);
+                    DayRun {
+                        day,
+                        status: DayStatus::from_report(&report),
+                        reports: report.parts,
+                    }
+                }
+                None => DayRun {
+                    day,
+                    reports: vec![],
+                    status: DayStatus::Missing,
+                },
+            };
+
+            collector.finish(day_run);
+        }
+    } else {
+        // build up front, so that compiling does not count towards the timeout.
+        let executables = child_commands::build_solutions(year, &days_to_run, is_release);
+
+        if jobs > 1 && bench.is_none() {
+            // concurrent days would skew each others benchmarks, so benched runs are never parallel.
+            run_parallel(
+                year,
+                &days_to_run,
+                &executables,
+                jobs,
+                timeout,
+                |day_run, output| {
+                    collector.print_header(day_run.day);
+                    output.iter().for_each(OutputLine::print);
+                    collector.finish(day_run);
+                },
+            );
+        } else {
+            for &day in &days_to_run {
+                collector.print_header(day);
+
+                let options = RunOptions {
+                    bench,
+                    timeout,
+                    input: None,
+                };
+
+                let day_run = child_commands::run_solution(
+                    year,
+                    day,
+                    executables.get(&day).map(PathBuf::as_path),
+                    &options,
+                    &|line| line.print(),
+                );
+
+                collector.finish(day_run);
             }
-        });
+        }
+    }
+
+    let Collector { days, timings, .. } = collector;
 
-    if is_timed {
+    let timings = if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,14 +185,154 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun { days, timings }
+}
+
+/// Prints the per-day output and gathers the results of a run.
+struct Collector {
+    days: Vec<DayRun>,
+    timings: Vec<Timing>,
+    need_space: bool,
+}
+
+impl Collector {
+    fn print_header(&mut self, day: Day) {
+        if self.need_space {
+            println!();
+        }
+        self.need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    }
+
+    fn finish(&mut self, day_run: DayRun) {
+        match &day_run.status {
+            DayStatus::Solved | DayStatus::Unsolved => {}
+            DayStatus::Panicked(message) => {
+                eprintln!("Panicked: {}", message.as_deref().unwrap_or("no message"));
+            }
+            DayStatus::TimedOut(timeout) => eprintln!("Timed out after {timeout:?}."),
+            DayStatus::Failed(reason) => eprintln!("Failed: {reason}"),
+            DayStatus::Missing => println!("Not scaffolded."),
+        }
+
+        if !day_run.reports.is_empty() {
+            self.timings
+                .push(child_commands::build_timing(&day_run.reports, day_run.day));
+        }
+
+        self.days.push(day_run);
+    }
+}
+
+/// Run the solution bins of `days` on `jobs` worker threads, buffering their output.
+/// Results are handed to `on_done` in the order of `days` as soon as all previous days are done.
+fn run_parallel(
+    year: Year,
+    days: &[Day],
+    executables: &HashMap<Day, PathBuf>,
+    jobs: usize,
+    timeout: Option<Duration>,
+    mut on_done: impl FnMut(DayRun, Vec<OutputLine>),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|s| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+
+                let options = RunOptions {
+                    bench: None,
+                    timeout,
+                    input: None,
+                };
+
+                let output = Mutex::new(vec![]);
+                let day_run = child_commands::run_solution(
+                    year,
+                    day,
+                    executables.get(&day).map(PathBuf::as_path),
+                    &options,
+                    &|line| {
+                        output.lock().unwrap().push(line);
+                    },
+                );
+
+                if tx.send((i, day_run, output.into_inner().unwrap())).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // only the workers hold senders now, so the loop ends once all of them are done.
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (i, day_run, output) in rx {
+            pending.insert(i, (day_run, output));
+
+            while let Some((day_run, output)) = pending.remove(&next_to_print) {
+                on_done(day_run, output);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+/// Print how many days were solved, unsolved, panicked or timed out, and list every day that did not succeed.
+pub fn print_summary(days: &[DayRun]) {
+    let count = |f: fn(&DayStatus) -> bool| days.iter().filter(|d| f(&d.status)).count();
+
+    let failed = count(|s| matches!(s, DayStatus::Failed(_)));
+
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+    println!(
+        "{} solved, {} unsolved, {} panicked, {} timed out{}",
+        count(|s| *s == DayStatus::Solved),
+        count(|s| *s == DayStatus::Unsolved),
+        count(|s| matches!(s, DayStatus::Panicked(_))),
+        count(|s| matches!(s, DayStatus::TimedOut(_))),
+        if failed > 0 {
+            format!(", {failed} failed")
+        } else {
+            String::new()
+        }
+    );
+
+    for day_run in days {
+        let details = match &day_run.status {
+            DayStatus::Solved | DayStatus::Missing => continue,
+            DayStatus::Unsolved => "unsolved".into(),
+            DayStatus::Panicked(message) => {
+                format!("panicked: {}", message.as_deref().unwrap_or("no message"))
+            }
+            DayStatus::TimedOut(timeout) => format!("timed out after {timeout:?}"),
+            DayStatus::Failed(reason) => format!("failed: {reason}"),
+        };
+
+        println!("Day {}: {details}", day_run.day);
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Report(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -59,199 +341,451 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => f.write_str("could not capture the output of the solution."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::Report(e) => write!(f, "invalid report: {e}"),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their part reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, DayRun, DayStatus, Error};
+    use crate::template::{
+        bin_name,
+        report::{read_reports, PartReport, PartStatus, RunReport, PARSE_PART, REPORT_FILE_ENV},
+        runner::BenchConfig,
+        timings::PartTiming,
+        Day, Year,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        env, fs,
+        io::{self, BufRead, BufReader, Read},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// A line printed by a solution bin.
+    pub enum OutputLine {
+        Stdout(String),
+        Stderr(String),
+    }
+
+    impl OutputLine {
+        /// Forward the line to the stream it was printed to.
+        pub fn print(&self) {
+            match self {
+                OutputLine::Stdout(line) => println!("{line}"),
+                OutputLine::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+
+    /// How to invoke a solution bin.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct RunOptions<'a> {
+        /// Bench configuration, the parts are executed once if absent.
+        pub bench: Option<BenchConfig>,
+        /// Wall-clock limit, the bin is killed if it does not finish in time.
+        pub timeout: Option<Duration>,
+        /// File passed to the bin with `--input`, instead of the puzzle input.
+        pub input: Option<&'a Path>,
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, handing each line of its output to `on_output`.
+    /// `executable` is the bin built by [`build_solutions`], the day fails if it could not be built.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        executable: Option<&Path>,
+        options: &RunOptions,
+        on_output: &(dyn Fn(OutputLine) + Sync),
+    ) -> DayRun {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return DayRun {
+                day,
+                reports: vec![],
+                status: DayStatus::Missing,
+            };
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let Some(executable) = executable else {
+            return DayRun {
+                day,
+                reports: vec![],
+                status: DayStatus::Failed("the solution could not be built.".into()),
+            };
+        };
 
-        if is_release {
-            args.push("--release");
+        let report_path = get_report_path(year, day);
+        let _ = fs::remove_file(&report_path);
+
+        let result = run_child(executable, options, &report_path, on_output);
+        let _ = fs::remove_file(&report_path);
+
+        match result {
+            Ok((report, status)) => DayRun {
+                day,
+                reports: report.parts,
+                status,
+            },
+            Err(e) => DayRun {
+                day,
+                reports: vec![],
+                status: DayStatus::Failed(e.to_string()),
+            },
         }
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    fn run_child(
+        executable: &Path,
+        options: &RunOptions,
+        report_path: &Path,
+        on_output: &(dyn Fn(OutputLine) + Sync),
+    ) -> Result<(RunReport, DayStatus), Error> {
+        let mut args: Vec<String> = vec![];
+
+        if let Some(bench) = options.bench {
+            // mirror `--time` flag and bench configuration to child invocations.
+            args.push("--time".into());
+            args.extend(bench.to_args());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        if let Some(input) = options.input {
+            args.push("--input".into());
+            args.push(input.to_string_lossy().into_owned());
+        }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        // the bin is spawned directly instead of through `cargo run`, so that a timeout kills the solution itself.
+        // output is handed to `on_output`, results are collected through the report file.
+        let mut cmd = Command::new(executable);
+        cmd.args(&args).env(REPORT_FILE_ENV, report_path);
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let exit_status = run_forwarding(&mut cmd, options.timeout, on_output)?;
 
-        let mut output = vec![];
+        let report = read_reports(report_path).map_err(Error::Report)?;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let status = match exit_status {
+            None => DayStatus::TimedOut(options.timeout.unwrap_or_default()),
+            // NOTE: solutions also exit with an error if an answer does not match the accepted one, after reporting.
+            Some(exit_status)
+                if !exit_status.success() && report.parts.is_empty() && report.panic.is_none() =>
+            {
+                DayStatus::Failed(exit_status.to_string())
+            }
+            Some(_) => DayStatus::from_report(&report),
+        };
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        Ok((report, status))
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    /// Spawn `cmd` with piped stdout/stderr and hand its output to `on_output` until it exits.
+    /// Returns [`None`] if it was killed after exceeding the timeout.
+    fn run_forwarding(
+        cmd: &mut Command,
+        timeout: Option<Duration>,
+        on_output: &(dyn Fn(OutputLine) + Sync),
+    ) -> Result<Option<ExitStatus>, Error> {
+        let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+        let stdout = child.stdout.take().ok_or(Error::BrokenPipe)?;
+        let stderr = child.stderr.take().ok_or(Error::BrokenPipe)?;
+
+        let exit_status = thread::scope(|s| {
+            s.spawn(|| forward_lines(stderr, OutputLine::Stderr, on_output));
+            s.spawn(|| forward_lines(stdout, OutputLine::Stdout, on_output));
+            wait_with_timeout(&mut child, timeout)
+        })?;
+
+        Ok(exit_status)
+    }
 
-        Ok(output)
+    /// Hand every line of `stream` to `on_output`. Lines that are not valid UTF-8 are converted lossily.
+    fn forward_lines(
+        stream: impl Read,
+        to_line: fn(String) -> OutputLine,
+        on_output: &(dyn Fn(OutputLine) + Sync),
+    ) {
+        for line in BufReader::new(stream).split(b'\n').map_while(Result::ok) {
+            let line = String::from_utf8_lossy(&line);
+            on_output(to_line(line.trim_end_matches('\r').to_string()));
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Wait for the child to exit. Returns [`None`] if it was killed after exceeding the timeout.
+    fn wait_with_timeout(
+        child: &mut Child,
+        timeout: Option<Duration>,
+    ) -> io::Result<Option<ExitStatus>> {
+        let Some(timeout) = timeout else {
+            return child.wait().map(Some);
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let deadline = Instant::now() + timeout;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok(None);
+            }
 
-                timings.total_nanos += nanos;
-            });
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
 
-        timings
+    /// Build the solution bins of `days` in a single cargo invocation, so that concurrent runs do not wait on each
+    /// other for the build lock. Compile errors are printed, days whose bin failed to build have no executable.
+    pub fn build_solutions(year: Year, days: &[Day], is_release: bool) -> HashMap<Day, PathBuf> {
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--keep-going".into(),
+            "--message-format=json-diagnostic-rendered-ansi".into(),
+        ];
+
+        let mut bins = HashMap::new();
+
+        for day in days {
+            if Path::new(&get_path_for_bin(year, *day)).exists() {
+                args.push("--bin".into());
+                args.push(bin_name(year, *day));
+                bins.insert(bin_name(year, *day), *day);
+            }
+        }
+
+        if bins.is_empty() {
+            return HashMap::new();
+        }
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        let Ok(output) = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()
+        else {
+            return HashMap::new();
+        };
+
+        // NOTE: executables are taken from the build messages, a bin that failed to build may still have a stale
+        // executable from an earlier build in the target directory.
+        let mut executables = HashMap::new();
+
+        for message in String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.parse::<JsonValue>().ok())
+        {
+            match get_str(&message, &["reason"]) {
+                Some("compiler-message")
+                    if get_str(&message, &["message", "level"]) == Some("error") =>
+                {
+                    if let Some(rendered) = get_str(&message, &["message", "rendered"]) {
+                        eprint!("{rendered}");
+                    }
+                }
+                Some("compiler-artifact") => {
+                    let day =
+                        get_str(&message, &["target", "name"]).and_then(|name| bins.get(name));
+                    let executable = get_str(&message, &["executable"]);
+
+                    if let (Some(day), Some(executable)) = (day, executable) {
+                        executables.insert(*day, PathBuf::from(executable));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        executables
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Look up the string at `path` in nested JSON objects.
+    fn get_str<'a>(json: &'a JsonValue, path: &[&str]) -> Option<&'a str> {
+        path.iter()
+            .try_fold(json, |json, key| {
+                json.get::<HashMap<String, JsonValue>>()?.get(*key)
+            })?
+            .get::<String>()
+            .map(String::as_str)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn get_report_path(year: Year, day: Day) -> PathBuf {
+        env::temp_dir().join(format!(
+            "aoc-report-{}-{}.jsonl",
+            process::id(),
+            bin_name(year, day)
+        ))
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    pub fn build_timing(reports: &[PartReport], day: Day) -> super::Timing {
+        let mut timings = super::Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+        };
+
+        reports
+            .iter()
+            .filter(|r| r.status != PartStatus::Unsolved)
+            .for_each(|r| {
+                let timing = Some(PartTiming {
+                    nanos: r.duration_nanos,
+                    samples: r.samples,
+                    stats: r.stats,
+                    failed: matches!(r.status, PartStatus::Failed(_)),
+                });
+
+                match r.part {
+                    PARSE_PART => timings.parse = timing,
+                    1 => timings.part_1 = timing,
+                    2 => timings.part_2 = timing,
+                    _ => {}
+                }
+            });
+
+        timings
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::build_timing;
+
+        use crate::{
+            day,
+            template::{
+                report::{PartReport, PartStatus, RunReport},
+                run_multi::DayStatus,
+            },
+        };
 
-        use crate::day;
+        fn report(part: u8, answer: Option<&str>, duration_nanos: u128) -> PartReport {
+            PartReport {
+                part,
+                answer: answer.map(Into::into),
+                duration_nanos,
+                samples: 100,
+                stats: None,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+        fn builds_execution_times() {
+            let res = build_timing(
+                &[report(1, Some("0"), 74), report(2, Some("10"), 74_130_000)],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos(), 74_130_074);
+            assert_eq!(res.part_1.unwrap().nanos, 74);
+            assert_eq!(res.part_1.unwrap().samples, 100);
+            assert_eq!(res.part_2.unwrap().nanos, 74_130_000);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn builds_with_patterns_in_answer() {
+            let res = build_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms"), 2_000_000_000),
+                    report(2, Some("10s\n(1 samples)"), 100_000_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos(), 2_100_000_000);
+            assert_eq!(res.part_1.unwrap().nanos, 2_000_000_000);
+            assert_eq!(res.part_2.unwrap().nanos, 100_000_000);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn builds_missing_parts() {
+            let res = build_timing(&[report(1, None, 10), report(2, None, 10)], day!(1));
+            assert_eq!(res.total_nanos(), 0);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+        }
+
+        #[test]
+        fn builds_failed_parts() {
+            let failed = PartReport {
+                status: PartStatus::Failed("invalid digit".into()),
+                ..report(2, None, 20)
+            };
+            let res = build_timing(&[report(1, Some("1"), 10), failed], day!(1));
+            assert!(!res.part_1.unwrap().failed);
+            assert!(res.part_2.unwrap().failed);
+            assert_eq!(res.total_nanos(), 10);
+        }
+
+        #[test]
+        fn classifies_day_status() {
+            let run_report = |parts: Vec<PartReport>, panic: Option<&str>| RunReport {
+                parts,
+                panic: panic.map(Into::into),
+            };
+
+            assert_eq!(
+                DayStatus::from_report(&run_report(
+                    vec![report(0, Some("parsed"), 1), report(1, Some("1"), 1)],
+                    None
+                )),
+                DayStatus::Solved
+            );
+            assert_eq!(
+                DayStatus::from_report(&run_report(
+                    vec![report(1, Some("1"), 1), report(2, None, 1)],
+                    None
+                )),
+                DayStatus::Unsolved
+            );
+            assert_eq!(
+                DayStatus::from_report(&run_report(vec![], None)),
+                DayStatus::Unsolved
+            );
+            assert_eq!(
+                DayStatus::from_report(&run_report(
+                    vec![report(1, Some("1"), 1)],
+                    Some("overflow")
+                )),
+                DayStatus::Panicked(Some("overflow".into()))
+            );
+            assert_eq!(
+                DayStatus::from_report(&run_report(
+                    vec![
+                        report(1, Some("1"), 1),
+                        PartReport {
+                            status: PartStatus::Failed("invalid digit".into()),
+                            ..report(2, None, 1)
+                        }
+                    ],
+                    None
+                )),
+                DayStatus::Failed("part 2: invalid digit".into())
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
@@ -1,35 +1,226 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::cell::RefCell;
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Write};
+use std::panic::{self, UnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use crate::template::answers::{Answers, Verdict};
+use crate::template::aoc_client::{AocClient, AocClientError, Submission, SubmissionOutcome};
+use crate::template::report::{self, PartReport, PartStatus, RunReport, PARSE_PART};
+use crate::template::stats::BenchStats;
+use crate::template::submissions::SubmissionLog;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{data_dir, read_file, Day, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Return type of a solution part: `Option<T>`, where `None` marks an unsolved part, or `Result<T, E>` for parts
+/// that can fail. The error of a failed part is printed with its source chain in place of the answer.
+/// Any error that converts into `Box<dyn Error>` is supported, e.g. `String`, `&str` or an `std::error::Error`.
+pub trait PartOutput {
+    type Answer: Display;
+
+    /// The answer of the part, if it returned one.
+    fn answer(&self) -> Option<&Self::Answer>;
+
+    /// The answer of the part, or the messages of its error and the error's sources if the part failed.
+    fn into_result(self) -> Result<Option<Self::Answer>, Vec<String>>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn answer(&self) -> Option<&T> {
+        self.as_ref()
+    }
+
+    fn into_result(self) -> Result<Option<T>, Vec<String>> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<T, E> {
+    type Answer = T;
+
+    fn answer(&self) -> Option<&T> {
+        self.as_ref().ok()
+    }
+
+    fn into_result(self) -> Result<Option<T>, Vec<String>> {
+        self.map(Some).map_err(|e| error_chain(e.into().as_ref()))
+    }
+}
+
+/// Messages of an error and all of its sources.
+fn error_chain(error: &dyn Error) -> Vec<String> {
+    let mut chain = vec![error.to_string()];
+    let mut source = error.source();
+
+    while let Some(error) = source {
+        chain.push(error.to_string());
+        source = error.source();
+    }
+
+    chain
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples, stats) = run_timed(func, input, |result| {
+        print_result(&result.answer(), &part_str, "");
+    });
+
+    let result = match result.into_result() {
+        Ok(result) => result,
+        Err(chain) => {
+            // a failed part is reported, but never checked, accepted or submitted.
+            print_error(
+                &chain,
+                &part_str,
+                &format_duration(&duration, samples, stats.as_ref()),
+            );
+            PART_FAILED.store(true, Ordering::Relaxed);
+
+            emit_report(PartReport {
+                part,
+                answer: None,
+                duration_nanos: duration.as_nanos(),
+                samples,
+                stats,
+                status: PartStatus::Failed(chain.join(": ")),
+            });
+            return;
+        }
+    };
+
+    let answer = result.as_ref().map(ToString::to_string);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    // accepted answers only apply to the puzzle input.
+    let verdict = if get_input_arg().is_some() || is_example() {
+        Verdict::Unknown
+    } else {
+        check_answer(answer.as_deref(), year, day, part)
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_duration(&duration, samples, stats.as_ref()),
+            format_verdict(&verdict)
+        ),
+    );
+
+    if let Verdict::Incorrect { expected } = &verdict {
+        eprintln!(
+            "{part_str}: ✗ answer {} does not match accepted answer {expected}",
+            answer.as_deref().unwrap_or("✖")
+        );
+        ANSWER_MISMATCH.store(true, Ordering::Relaxed);
+    }
+
+    let report = PartReport {
+        part,
+        answer: answer.clone(),
+        duration_nanos: duration.as_nanos(),
+        samples,
+        stats,
+        status: if result.is_some() {
+            PartStatus::Solved
+        } else {
+            PartStatus::Unsolved
+        },
+    };
+
+    emit_report(report);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(answer) = answer {
+        // `solve` flags only apply to solutions running in their own binary.
+        if !is_in_process() {
+            accept_result(&answer, year, day, part);
+            submit_result(answer, year, day, part);
+        }
+    }
+}
+
+/// Set when a part's answer does not match its accepted answer.
+static ANSWER_MISMATCH: AtomicBool = AtomicBool::new(false);
+
+/// Set when a part returned an error.
+static PART_FAILED: AtomicBool = AtomicBool::new(false);
+
+/// Exit with a non-zero status if any part did not match its accepted answer or failed.
+pub fn finish() {
+    if ANSWER_MISMATCH.load(Ordering::Relaxed) || PART_FAILED.load(Ordering::Relaxed) {
+        process::exit(1);
     }
 }
 
+fn check_answer(answer: Option<&str>, year: Year, day: Day, part: u8) -> Verdict {
+    match Answers::read(year, day) {
+        Ok(answers) => answers.check(part, answer),
+        Err(e) => {
+            eprintln!("Failed to read accepted answers: {e}");
+            Verdict::Unknown
+        }
+    }
+}
+
+fn format_verdict(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => " ✓",
+        Verdict::Incorrect { .. } => " ✗",
+        Verdict::Unknown => "",
+    }
+}
+
+/// Run the parse step of a solution and return the parsed value, which is then shared by the parts.
+/// The parse step is timed like a part, and reported separately as `Parse`.
+pub fn run_parse<'a, P>(func: impl Fn(&'a str) -> P, input: &'a str) -> P {
+    let (parsed, duration, samples, stats) = run_timed(func, input, |_| print!("Parse:"));
+
+    println!(
+        "\rParse:{}",
+        format_duration(&duration, samples, stats.as_ref())
+    );
+
+    let report = PartReport {
+        part: PARSE_PART,
+        answer: None,
+        duration_nanos: duration.as_nanos(),
+        samples,
+        stats,
+        status: PartStatus::Solved,
+    };
+
+    emit_report(report);
+
+    parsed
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// The returned duration is the mean of all samples when benched.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<BenchStats>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,23 +234,132 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if let Some(config) = get_bench_config() {
+        let stats = bench(func, input, &base_time, &config);
+        (result, stats.mean, stats.samples as u128, Some(stats))
     } else {
-        (base_time, 1)
-    };
+        (result, base_time, 1, None)
+    }
+}
+
+/// State of a solution that is executed in-process, see [`run_in_process`].
+struct InProcessRun {
+    bench: Option<BenchConfig>,
+    reports: Vec<PartReport>,
+}
+
+thread_local! {
+    static IN_PROCESS_RUN: RefCell<Option<InProcessRun>> = const { RefCell::new(None) };
+}
+
+/// Execute a linked solution on the current thread, benching its parts with `bench` if present.
+/// Instead of reading command-line arguments and writing a report file, the runner collects the part
+/// reports in memory. A panicking solution yields the reports of all parts that completed before the panic.
+pub(crate) fn run_in_process(
+    bench: Option<BenchConfig>,
+    solution: impl FnOnce() + UnwindSafe,
+) -> RunReport {
+    IN_PROCESS_RUN.with_borrow_mut(|run| {
+        *run = Some(InProcessRun {
+            bench,
+            reports: vec![],
+        });
+    });
+
+    let panic = panic::catch_unwind(solution)
+        .err()
+        .map(|payload| panic_message(payload.as_ref()));
+
+    let parts = IN_PROCESS_RUN
+        .with_borrow_mut(Option::take)
+        .map(|run| run.reports)
+        .unwrap_or_default();
+
+    RunReport { parts, panic }
+}
+
+/// Report panics of a solution bin to the parent process, in addition to printing them as usual.
+pub fn report_panics() {
+    let print_panic = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        print_panic(info);
+
+        let message = panic_message(info.payload());
+        let _ = match info.location() {
+            Some(location) => report::emit_panic(&format!("{message} ({location})")),
+            None => report::emit_panic(&message),
+        };
+    }));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".into())
+}
+
+fn is_in_process() -> bool {
+    IN_PROCESS_RUN.with_borrow(Option::is_some)
+}
+
+/// Resolve the bench configuration, or [`None`] if the part should only be executed once.
+fn get_bench_config() -> Option<BenchConfig> {
+    if let Some(bench) = IN_PROCESS_RUN.with_borrow(|run| run.as_ref().map(|run| run.bench)) {
+        return bench;
+    }
+
+    let args: Vec<String> = env::args().collect();
+
+    if !args.iter().any(|x| x == "--time") {
+        return None;
+    }
+
+    let config = BenchConfig::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Invalid bench configuration: {e}");
+        process::exit(1);
+    });
 
-    (result, run.0, run.1)
+    Some(config)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Hand a part report to the parent: either the in-process run or the report file.
+fn emit_report(report: PartReport) {
+    let report = IN_PROCESS_RUN.with_borrow_mut(|run| match run {
+        Some(run) => {
+            run.reports.push(report);
+            None
+        }
+        None => Some(report),
+    });
+
+    if let Some(report) = report {
+        if let Err(e) = report.emit() {
+            eprintln!("Failed to write part report: {e}");
+        }
+    }
+}
+
+/// Bench a solution part and summarize the samples.
+/// A warm-up phase of a tenth of the measured iterations is executed first and discarded.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> BenchStats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = config.iterations(base_time);
+
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,26 +371,147 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers).expect("bench collects at least one sample")
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+const BUDGET_ENV: &str = "AOC_BENCH_BUDGET";
+const MIN_SAMPLES_ENV: &str = "AOC_BENCH_MIN_SAMPLES";
+const MAX_SAMPLES_ENV: &str = "AOC_BENCH_MAX_SAMPLES";
+
+/// Controls how long and how often a part is sampled when benched.
+///
+/// Values are resolved from the defaults, then the `AOC_BENCH_BUDGET`, `AOC_BENCH_MIN_SAMPLES` and
+/// `AOC_BENCH_MAX_SAMPLES` environment variables (e.g. set in `.cargo/config.toml`), then command-line flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time spent sampling a part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the configuration from the environment, falling back to defaults for unset variables.
+    pub fn from_env() -> Result<Self, String> {
+        let var = |key: &str| env::var(key).ok().filter(|x| !x.is_empty());
+
+        Self::default().with_overrides(
+            var(BUDGET_ENV).as_deref().map(parse_duration).transpose()?,
+            var(MIN_SAMPLES_ENV)
+                .as_deref()
+                .map(parse_samples)
+                .transpose()?,
+            var(MAX_SAMPLES_ENV)
+                .as_deref()
+                .map(parse_samples)
+                .transpose()?,
+        )
+    }
+
+    /// Replace the values that are present and validate the result.
+    pub fn with_overrides(
+        self,
+        budget: Option<Duration>,
+        min_samples: Option<u128>,
+        max_samples: Option<u128>,
+    ) -> Result<Self, String> {
+        let config = Self {
+            budget: budget.unwrap_or(self.budget),
+            min_samples: min_samples.unwrap_or(self.min_samples),
+            max_samples: max_samples.unwrap_or(self.max_samples),
+        };
+
+        if config.min_samples == 0 {
+            return Err("minimum sample count must be at least 1.".into());
+        }
+
+        if config.min_samples > config.max_samples {
+            return Err(format!(
+                "minimum sample count {} exceeds maximum sample count {}.",
+                config.min_samples, config.max_samples
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// Command-line arguments that mirror this configuration to a child invocation.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--budget".into(),
+            format!("{}s", self.budget.as_secs_f64()),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+
+    fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| {
+                    args.get(i + 1)
+                        .map(String::as_str)
+                        .ok_or(format!("missing value for `{flag}`."))
+                })
+                .transpose()
+        };
+
+        Self::from_env()?.with_overrides(
+            value("--budget")?.map(parse_duration).transpose()?,
+            value("--min-samples")?.map(parse_samples).transpose()?,
+            value("--max-samples")?.map(parse_samples).transpose()?,
+        )
+    }
+
+    /// Number of measured iterations for a part whose first execution took `base_time`.
+    fn iterations(&self, base_time: &Duration) -> u128 {
+        (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+/// Parse a duration such as `500ms`, `2s` or `1.5s`. Plain numbers are interpreted as milliseconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let err = || format!("invalid duration `{s}`, expecting e.g. `500ms` or `2s`.");
+
+    let (value, unit) = match s {
+        s if s.ends_with("ms") => (&s[..s.len() - 2], 1e-3),
+        s if s.ends_with('s') => (&s[..s.len() - 1], 1.0),
+        s => (s, 1e-3),
+    };
+
+    let value: f64 = value.trim().parse().map_err(|_| err())?;
+    Duration::try_from_secs_f64(value * unit).map_err(|_| err())
+}
+
+fn parse_samples(s: &str) -> Result<u128, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("invalid sample count `{s}`."))
+}
+
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&BenchStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            " ({duration:.1?} @ {samples} samples; min {:.1?}, median {:.1?}, p95 {:.1?}, σ {:.1?}, {} outliers)",
+            stats.min, stats.median, stats.p95, stats.std_dev, stats.outliers
+        ),
+        None if samples == 1 => format!(" ({duration:.1?})"),
+        None => format!(" ({duration:.1?} @ {samples} samples)"),
     }
 }
 
@@ -129,41 +550,328 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Print the error of a failed part, followed by the errors that caused it.
+fn print_error(chain: &[String], part: &str, duration_str: &str) {
+    print!("\r");
+    println!(
+        "{part}: ✖ {ANSI_BOLD}error:{ANSI_RESET} {}{duration_str}",
+        chain[0]
+    );
+    for cause in &chain[1..] {
+        println!("  caused by: {cause}");
+    }
+}
+
+/// The input of the parts of a solution. Parts share the puzzle input, but can have their own example.
+pub enum PartInputs {
+    Shared(String),
+    Split([String; 2]),
+}
+
+impl PartInputs {
+    /// The input of both parts, or [`None`] if every part has its own.
+    pub fn shared(&self) -> Option<&str> {
+        match self {
+            Self::Shared(input) => Some(input),
+            Self::Split(_) => None,
+        }
+    }
+
+    pub fn part(&self, part: u8) -> &str {
+        match self {
+            Self::Shared(input) => input,
+            Self::Split([part_one, part_two]) => {
+                if part == 1 {
+                    part_one
+                } else {
+                    part_two
+                }
+            }
+        }
+    }
+}
+
+/// Read the input of a day. Solution binaries read the file passed with `--input <path>`, or stdin for
+/// `--input -`, and fall back to `data/<year>/inputs/<day>.txt`.
+/// With `--example`, the parts read `data/<year>/examples/<day>.txt`, or their own `<day>-<part>.txt` if split.
+pub fn read_input(year: Year, day: Day) -> PartInputs {
+    let custom_input = get_input_arg();
+    let example = is_example();
+
+    if (custom_input.is_some() || example)
+        && (get_part_arg("--submit").is_some() || get_part_arg("--accept").is_some())
+    {
+        eprintln!("Answers for a custom input cannot be submitted or accepted. Remove --input or --example to use your puzzle input.");
+        process::exit(1);
+    }
+
+    if example {
+        if custom_input.is_some() {
+            eprintln!("--input and --example cannot be combined.");
+            process::exit(1);
+        }
+        return read_example(year, day);
+    }
+
+    let Some(path) = custom_input else {
+        return PartInputs::Shared(read_file("inputs", year, day));
+    };
+
+    let input = if path == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(&path)
+    };
+
+    PartInputs::Shared(input.unwrap_or_else(|e| {
+        eprintln!("Failed to read input `{path}`: {e}");
+        process::exit(1);
+    }))
+}
+
+/// Read the example of a day. If split examples exist, a part without its own file falls back to the shared one.
+fn read_example(year: Year, day: Day) -> PartInputs {
+    let examples = data_dir(year).join("examples");
+    let shared_path = examples.join(format!("{day}.txt"));
+    let part_paths = [1, 2].map(|part| examples.join(format!("{day}-{part}.txt")));
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read example `{}`: {e}", path.display());
+            process::exit(1);
+        })
+    };
+
+    if part_paths.iter().any(|path| path.exists()) {
+        PartInputs::Split(part_paths.map(|path| {
+            if path.exists() {
+                read(&path)
+            } else {
+                read(&shared_path)
+            }
+        }))
+    } else {
+        PartInputs::Shared(read(&shared_path))
+    }
+}
+
+/// Whether `--example` was passed to `solve`.
+fn is_example() -> bool {
+    !is_in_process() && env::args().any(|x| x == "--example")
+}
+
+/// Read the path passed to `--input`, if any.
+fn get_input_arg() -> Option<String> {
+    if is_in_process() {
+        return None;
+    }
+
+    let args: Vec<String> = env::args().collect();
+    let path_index = args.iter().position(|x| x == "--input")? + 1;
+
+    let Some(path) = args.get(path_index) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --input <path>");
+        process::exit(1);
+    };
+
+    Some(path.clone())
+}
+
+/// Read the part number passed to `flag`, e.g. `--submit 1`.
+fn get_part_arg(flag: &str) -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+
+    let part_index = args.iter().position(|x| x == flag)? + 1;
+
+    let Some(part) = args.get(part_index).and_then(|x| x.parse::<u8>().ok()) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 {flag} 1");
+        process::exit(1);
+    };
+
+    Some(part)
+}
+
+/// Record the answer of a part as accepted if `--accept <part>` was passed to `solve`.
+fn accept_result(answer: &str, year: Year, day: Day, part: u8) {
+    if get_part_arg("--accept") != Some(part) {
+        return;
+    }
+
+    let mut answers = Answers::read(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read accepted answers: {e}");
+        process::exit(1);
+    });
+    answers.set(part, answer.to_string());
+
+    match answers.store(year, day) {
+        Ok(()) => println!("Recorded {answer} as accepted answer for part {part}."),
+        Err(e) => {
+            eprintln!("Failed to store accepted answer: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
+/// Answers that earlier submissions prove wrong are refused unless `--force` is passed.
+/// Every verdict is recorded in the submission log, and correct answers are recorded as accepted answers.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+) -> Option<Result<Submission, AocClientError>> {
+    if get_part_arg("--submit")? != part {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let answer = result.to_string();
+
+    let mut log = SubmissionLog::read(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read submission log: {e}");
         process::exit(1);
-    }
+    });
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if let Err(rejection) = log.check(part, &answer) {
+        if env::args().any(|x| x == "--force") {
+            eprintln!("Submitting anyway: {rejection}");
+        } else {
+            eprintln!("Refusing to submit {answer}: {rejection} Pass --force to submit anyway.");
+            process::exit(1);
+        }
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to submit: {e}");
         process::exit(1);
+    });
+
+    println!("Submitting result...");
+    let submission = client.submit(year, day, part, &answer);
+
+    match &submission {
+        Ok(submission) => {
+            println!("{}", submission.message);
+
+            log.record(part, &answer, submission.outcome.clone());
+            if let Err(e) = log.store(year, day) {
+                eprintln!("Failed to store submission log: {e}");
+            }
+
+            if submission.outcome == SubmissionOutcome::Correct {
+                let mut answers = Answers::read(year, day).unwrap_or_default();
+                answers.set(part, answer);
+                if let Err(e) = answers.store(year, day) {
+                    eprintln!("Failed to store accepted answer: {e}");
+                }
+            }
+        }
+        Err(e) => eprintln!("Failed to submit: {e}"),
+    }
+
+    Some(submission)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, run_in_process, run_part, BenchConfig};
+    use crate::{
+        day,
+        template::{report::PartStatus, Year},
     };
+    use std::time::Duration;
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn collects_reports_in_process() {
+        let year = Year::new(2015).unwrap();
+        let report = run_in_process(None, || {
+            run_part(|x: u32| Some(x + 1), 1, year, day!(25), 1);
+            run_part(|_: u32| None::<u32>, 1, year, day!(25), 2);
+            panic!("solution panicked");
+        });
+
+        assert_eq!(report.panic.as_deref(), Some("solution panicked"));
+
+        let reports = report.parts;
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].answer, Some("2".into()));
+        assert_eq!(reports[0].samples, 1);
+        assert_eq!(reports[1].status, PartStatus::Unsolved);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn reports_failed_parts() {
+        #[derive(Debug)]
+        struct ParseError(std::num::ParseIntError);
+
+        impl std::fmt::Display for ParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "bad line 3")
+            }
+        }
+
+        impl std::error::Error for ParseError {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let year = Year::new(2015).unwrap();
+        let report = run_in_process(None, || {
+            run_part(|x: &str| x.parse::<u32>(), "7", year, day!(25), 1);
+            run_part(
+                |x: &str| x.parse::<u32>().map_err(ParseError),
+                "x",
+                year,
+                day!(25),
+                2,
+            );
+        });
+
+        let reports = report.parts;
+        assert_eq!(reports[0].answer, Some("7".into()));
+        assert_eq!(reports[0].status, PartStatus::Solved);
+        assert_eq!(reports[1].answer, None);
+        assert_eq!(
+            reports[1].status,
+            PartStatus::Failed("bad line 3: invalid digit found in string".into())
+        );
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250"), Ok(Duration::from_millis(250)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_sample_bounds() {
+        let config = BenchConfig::default();
+        assert!(config.with_overrides(None, Some(0), None).is_err());
+        assert!(config.with_overrides(None, Some(100), Some(10)).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let config = BenchConfig::default()
+            .with_overrides(Some(Duration::from_millis(200)), Some(3), Some(50))
+            .unwrap();
+        let mut args = vec!["01".to_string(), "--time".to_string()];
+        args.extend(config.to_args());
+        assert_eq!(BenchConfig::from_args(&args), Ok(config));
+    }
+
+    #[test]
+    fn clamps_iterations() {
+        let config = BenchConfig::default()
+            .with_overrides(Some(Duration::from_millis(100)), Some(5), Some(20))
+            .unwrap();
+        assert_eq!(config.iterations(&Duration::from_secs(1)), 5);
+        assert_eq!(config.iterations(&Duration::from_millis(10)), 10);
+        assert_eq!(config.iterations(&Duration::from_nanos(1)), 20);
+    }
 }
@@ -0,0 +1,312 @@
+/// Summary statistics over a set of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR below Q1 / above Q3).
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Compute statistics for the provided samples. Returns [`None`] if there are no samples.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let count = nanos.len() as f64;
+
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+        let (lower_fence, upper_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let outliers = nanos
+            .iter()
+            .filter(|x| **x < lower_fence || **x > upper_fence)
+            .count();
+
+        Some(Self {
+            samples: sorted.len(),
+            mean: Duration::from_nanos(mean as u64),
+            min: sorted[0],
+            median: Duration::from_nanos(percentile(&nanos, 50.0) as u64),
+            p95: Duration::from_nanos(percentile(&nanos, 95.0) as u64),
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            outliers,
+        })
+    }
+}
+
+/// Two-sided p-value of Welch's t-test for the difference of the means of two benchmarks.
+/// Returns [`None`] if either benchmark has fewer than two samples.
+#[allow(clippy::cast_precision_loss)]
+pub fn welch_p_value(a: &BenchStats, b: &BenchStats) -> Option<f64> {
+    if a.samples < 2 || b.samples < 2 {
+        return None;
+    }
+
+    // `std_dev` is the population standard deviation, convert to the unbiased sample variance.
+    let variance = |s: &BenchStats| {
+        let n = s.samples as f64;
+        (s.std_dev.as_nanos() as f64).powi(2) * n / (n - 1.0)
+    };
+
+    let (n_a, n_b) = (a.samples as f64, b.samples as f64);
+    let (se_a, se_b) = (variance(a) / n_a, variance(b) / n_b);
+    let diff = a.mean.as_nanos() as f64 - b.mean.as_nanos() as f64;
+
+    if se_a + se_b == 0.0 {
+        return Some(if diff == 0.0 { 1.0 } else { 0.0 });
+    }
+
+    let t = diff / (se_a + se_b).sqrt();
+    let df = (se_a + se_b).powi(2) / (se_a.powi(2) / (n_a - 1.0) + se_b.powi(2) / (n_b - 1.0));
+
+    Some(regularized_incomplete_beta(
+        df / (df + t * t),
+        df / 2.0,
+        0.5,
+    ))
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`, evaluated with a continued fraction.
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    // the continued fraction converges quickly for x < (a + 1) / (a + b + 2), use the symmetry otherwise.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Lentz's algorithm for the continued fraction of the incomplete beta function.
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const EPSILON: f64 = 1e-12;
+    const TINY: f64 = 1e-300;
+
+    let clamp = |v: f64| if v.abs() < TINY { TINY } else { v };
+
+    let mut c = 1.0;
+    let mut d = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+    let mut result = d;
+
+    for m in 1..=200 {
+        let m = f64::from(m);
+
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / clamp(1.0 + even * d);
+        c = clamp(1.0 + even / c);
+        result *= d * c;
+
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / clamp(1.0 + odd * d);
+        c = clamp(1.0 + odd / c);
+        let delta = d * c;
+        result *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let mut denominator = x;
+    let series = COEFFICIENTS.iter().fold(1.000_000_000_190_015, |acc, c| {
+        denominator += 1.0;
+        acc + c / denominator
+    });
+
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Linearly interpolated percentile of an ascending slice.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        Ok(BenchStats {
+            samples: number("samples")? as usize,
+            mean: duration("mean_nanos")?,
+            min: duration("min_nanos")?,
+            median: duration("median_nanos")?,
+            p95: duration("p95_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+/// Read an optional stats object from `key` of a JSON object. Missing keys and `null` yield [`None`].
+pub fn stats_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<BenchStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => BenchStats::try_from(v).map(Some),
+    }
+}
+
+/// Convert optional stats into a JSON value, using `null` for [`None`].
+pub fn stats_to_json(stats: Option<&BenchStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{welch_p_value, BenchStats};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = BenchStats::from_samples(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.p95, Duration::from_nanos(4));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats =
+            BenchStats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert!(stats.mean > stats.median);
+    }
+
+    #[test]
+    fn computes_welch_p_values() {
+        let a = BenchStats::from_samples(&nanos(&[100, 102, 98, 101, 99, 100, 103, 97])).unwrap();
+        let b = BenchStats::from_samples(&nanos(&[101, 99, 100, 102, 98, 100, 97, 103])).unwrap();
+        let c =
+            BenchStats::from_samples(&nanos(&[120, 122, 118, 121, 119, 120, 123, 117])).unwrap();
+
+        assert!(welch_p_value(&a, &b).unwrap() > 0.9);
+        assert!(welch_p_value(&a, &c).unwrap() < 0.001);
+        assert_eq!(welch_p_value(&a, &c), welch_p_value(&c, &a));
+
+        let single = BenchStats::from_samples(&nanos(&[100])).unwrap();
+        assert_eq!(welch_p_value(&a, &single), None);
+    }
+
+    #[test]
+    fn matches_reference_t_distribution() {
+        // t = 2.0 with 10 degrees of freedom has a two-sided p-value of 0.0734.
+        let p = super::regularized_incomplete_beta(10.0 / (10.0 + 4.0), 5.0, 0.5);
+        assert!((p - 0.0734).abs() < 1e-4, "{p}");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 20, 30, 40])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -0,0 +1,363 @@
+/// Log of submitted answers, used to avoid resubmitting answers that are known to be wrong.
+/// Submissions for a day live in `data/<year>/submissions/NN.json`.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{Hint, SubmissionOutcome},
+    data_dir, Day, Year,
+};
+
+/// A single submitted answer and the verdict of the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionRecord {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// All submissions of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub records: Vec<SubmissionRecord>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved { answer: String },
+    KnownWrong,
+    TooHigh { bound: i128 },
+    TooLow { bound: i128 },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Rejection::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Rejection::TooHigh { bound } => {
+                write!(f, "this answer is not below {bound}, which was too high.")
+            }
+            Rejection::TooLow { bound } => {
+                write!(f, "this answer is not above {bound}, which was too low.")
+            }
+        }
+    }
+}
+
+impl SubmissionLog {
+    /// Read the submissions of a day. If nothing was submitted yet, returns an empty log.
+    pub fn read(year: Year, day: Day) -> Result<Self, String> {
+        match fs::read_to_string(get_submissions_path(year, day)) {
+            Ok(contents) => SubmissionLog::try_from(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Persist the submissions of a day.
+    pub fn store(&self, year: Year, day: Day) -> Result<(), io::Error> {
+        let path = get_submissions_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// Append the outcome of a submission, timestamped with the current time.
+    pub fn record(&mut self, part: u8, answer: &str, outcome: SubmissionOutcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.records.push(SubmissionRecord {
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp,
+        });
+    }
+
+    /// Check whether `answer` is worth submitting for `part`, given earlier verdicts and hints.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Rejection> {
+        let records: Vec<_> = self.records.iter().filter(|r| r.part == part).collect();
+
+        if let Some(correct) = records
+            .iter()
+            .find(|r| r.outcome == SubmissionOutcome::Correct)
+        {
+            return Err(Rejection::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        let wrong = || {
+            records.iter().filter_map(|r| match r.outcome {
+                SubmissionOutcome::Incorrect { hint } => Some((r.answer.as_str(), hint)),
+                _ => None,
+            })
+        };
+
+        if wrong().any(|(wrong_answer, _)| wrong_answer == answer) {
+            return Err(Rejection::KnownWrong);
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |hint: Hint| {
+            wrong()
+                .filter(move |(_, h)| *h == Some(hint))
+                .filter_map(|(a, _)| a.trim().parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(Hint::TooHigh).min().filter(|b| value >= *b) {
+            return Err(Rejection::TooHigh { bound });
+        }
+
+        if let Some(bound) = bound(Hint::TooLow).max().filter(|b| value <= *b) {
+            return Err(Rejection::TooLow { bound });
+        }
+
+        Ok(())
+    }
+}
+
+fn get_submissions_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year)
+        .join("submissions")
+        .join(format!("{day}.json"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_str(outcome: &SubmissionOutcome) -> &'static str {
+    match outcome {
+        SubmissionOutcome::Correct => "correct",
+        SubmissionOutcome::Incorrect { .. } => "incorrect",
+        SubmissionOutcome::TooRecent { .. } => "too_recent",
+        SubmissionOutcome::WrongLevel => "wrong_level",
+        SubmissionOutcome::Unknown => "unknown",
+    }
+}
+
+fn hint_to_str(hint: Hint) -> &'static str {
+    match hint {
+        Hint::TooHigh => "too_high",
+        Hint::TooLow => "too_low",
+    }
+}
+
+fn hint_from_str(s: &str) -> Result<Hint, String> {
+    match s {
+        "too_high" => Ok(Hint::TooHigh),
+        "too_low" => Ok(Hint::TooLow),
+        x => Err(format!("Unknown hint `{x}`.")),
+    }
+}
+
+impl From<&SubmissionRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &SubmissionRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let hint = match value.outcome {
+            SubmissionOutcome::Incorrect { hint: Some(hint) } => {
+                JsonValue::String(hint_to_str(hint).into())
+            }
+            _ => JsonValue::Null,
+        };
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(outcome_to_str(&value.outcome).into()),
+        );
+        map.insert("hint".into(), hint);
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for SubmissionRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let hint = match json.get("hint") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(hint_from_str(
+                v.get::<String>()
+                    .ok_or("Expected submission.hint to be null or string.")?,
+            )?),
+        };
+
+        let outcome = match json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .as_str()
+        {
+            "correct" => SubmissionOutcome::Correct,
+            "incorrect" => SubmissionOutcome::Incorrect { hint },
+            "too_recent" => SubmissionOutcome::TooRecent { wait: None },
+            "wrong_level" => SubmissionOutcome::WrongLevel,
+            "unknown" => SubmissionOutcome::Unknown,
+            x => return Err(format!("Unknown verdict `{x}`.")),
+        };
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(SubmissionRecord {
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.records.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let records = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("submissions")
+            .ok_or("expected JSON document to have key `submissions`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.submissions` to be an array.")?;
+
+        Ok(SubmissionLog {
+            records: records
+                .iter()
+                .map(SubmissionRecord::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Rejection, SubmissionLog};
+    use crate::template::aoc_client::{Hint, SubmissionOutcome};
+    use tinyjson::JsonValue;
+
+    fn incorrect(hint: Option<Hint>) -> SubmissionOutcome {
+        SubmissionOutcome::Incorrect { hint }
+    }
+
+    #[test]
+    fn allows_first_submission() {
+        assert_eq!(SubmissionLog::default().check(1, "42"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let mut log = SubmissionLog::default();
+        log.record(1, "abc", incorrect(None));
+
+        assert_eq!(log.check(1, "abc"), Err(Rejection::KnownWrong));
+        assert_eq!(log.check(1, "abd"), Ok(()));
+        assert_eq!(log.check(2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_answers_outside_of_hinted_bounds() {
+        let mut log = SubmissionLog::default();
+        log.record(1, "100", incorrect(Some(Hint::TooHigh)));
+        log.record(1, "80", incorrect(Some(Hint::TooHigh)));
+        log.record(1, "20", incorrect(Some(Hint::TooLow)));
+
+        assert_eq!(log.check(1, "80"), Err(Rejection::KnownWrong));
+        assert_eq!(log.check(1, "90"), Err(Rejection::TooHigh { bound: 80 }));
+        assert_eq!(log.check(1, "5"), Err(Rejection::TooLow { bound: 20 }));
+        assert_eq!(log.check(1, "50"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_solved_parts() {
+        let mut log = SubmissionLog::default();
+        log.record(2, "7", SubmissionOutcome::TooRecent { wait: None });
+        log.record(2, "7", SubmissionOutcome::Correct);
+
+        assert_eq!(
+            log.check(2, "8"),
+            Err(Rejection::AlreadySolved { answer: "7".into() })
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut log = SubmissionLog::default();
+        log.record(1, "100", incorrect(Some(Hint::TooLow)));
+        log.record(1, "200", incorrect(None));
+        log.record(2, "3", SubmissionOutcome::Correct);
+
+        let json = JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_logs() {
+        SubmissionLog::try_from(r#"{ "submissions": [{ "part": 1 }] }"#.to_string()).unwrap();
+    }
+}
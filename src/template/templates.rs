@@ -0,0 +1,219 @@
+/// Templates for the solution modules created by `cargo scaffold`.
+/// Named templates are loaded at runtime from `<name>.txt` in the directory set as `AOC_TEMPLATE_DIR` (default:
+/// `templates`). Without a name, `default.txt` of that directory is used if present, otherwise the built-in template.
+use std::{env, fs, io, path::PathBuf};
+
+use crate::template::{Day, Year};
+
+const TEMPLATE_DIR_ENV: &str = "AOC_TEMPLATE_DIR";
+const ANSWER_TYPE_ENV: &str = "AOC_ANSWER_TYPE";
+const DEFAULT_TEMPLATE_DIR: &str = "templates";
+const DEFAULT_TEMPLATE: &str = "default";
+const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+const BUILTIN_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+pub fn get_template_dir() -> PathBuf {
+    env::var(TEMPLATE_DIR_ENV)
+        .ok()
+        .filter(|x| !x.is_empty())
+        .map_or_else(|| PathBuf::from(DEFAULT_TEMPLATE_DIR), PathBuf::from)
+}
+
+/// Names of the templates in the template directory, sorted.
+fn list_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(get_template_dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "txt").then(|| path.file_stem()?.to_str().map(Into::into))?
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Load the template with the given name, or the default template.
+pub fn load(name: Option<&str>) -> Result<String, String> {
+    let path = get_template_dir().join(format!("{}.txt", name.unwrap_or(DEFAULT_TEMPLATE)));
+
+    match (fs::read_to_string(&path), name) {
+        (Ok(template), _) => Ok(template),
+        (Err(e), None) if e.kind() == io::ErrorKind::NotFound => Ok(BUILTIN_TEMPLATE.into()),
+        (Err(e), Some(name)) if e.kind() == io::ErrorKind::NotFound => {
+            let available = list_templates();
+            Err(format!(
+                "template `{name}` not found at \"{}\". Available templates: {}",
+                path.display(),
+                if available.is_empty() {
+                    "none".into()
+                } else {
+                    available.join(", ")
+                }
+            ))
+        }
+        (Err(e), _) => Err(format!("failed to read \"{}\": {e}", path.display())),
+    }
+}
+
+/// Values substituted for the variables of a template.
+pub struct Variables {
+    pub year: Year,
+    pub day: Day,
+    pub title: String,
+    pub answer_type: String,
+}
+
+impl Variables {
+    /// Resolve the answer type from the argument, then `AOC_ANSWER_TYPE`, then the default `u32`.
+    pub fn new(year: Year, day: Day, title: Option<String>, answer_type: Option<String>) -> Self {
+        let answer_type = answer_type
+            .or_else(|| env::var(ANSWER_TYPE_ENV).ok())
+            .filter(|x| !x.is_empty())
+            .unwrap_or_else(|| DEFAULT_ANSWER_TYPE.into());
+
+        Self {
+            year,
+            day,
+            title: title.unwrap_or_else(|| format!("Day {}", day.into_inner())),
+            answer_type,
+        }
+    }
+
+    fn pairs(&self) -> [(&'static str, String); 5] {
+        [
+            ("YEAR", self.year.to_string()),
+            ("DAY_NUMBER", self.day.into_inner().to_string()),
+            ("DAY_PADDED", self.day.to_string()),
+            ("TITLE", self.title.clone()),
+            ("ANSWER_TYPE", self.answer_type.clone()),
+        ]
+    }
+}
+
+/// Substitute the `%NAME%` variables of a template.
+/// Returns the rendered module and the names of unknown variables, which are left as is.
+pub fn render(template: &str, variables: &Variables) -> (String, Vec<String>) {
+    let pairs = variables.pairs();
+    let mut output = String::with_capacity(template.len());
+    let mut unknown = vec![];
+    let mut rest = template;
+
+    while let Some(start) = rest.find('%') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let name = rest[1..]
+            .find('%')
+            .map(|end| &rest[1..=end])
+            .filter(|name| {
+                !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            });
+
+        let Some(name) = name else {
+            output.push('%');
+            rest = &rest[1..];
+            continue;
+        };
+
+        match pairs.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => output.push_str(value),
+            None => {
+                output.push_str(&rest[..name.len() + 2]);
+                if !unknown.iter().any(|x| x == name) {
+                    unknown.push(name.to_string());
+                }
+            }
+        }
+        rest = &rest[name.len() + 2..];
+    }
+
+    output.push_str(rest);
+    (output, unknown)
+}
+
+/// Point the example tests of a module to the examples of their part, `<day>-<part>.txt`.
+/// Returns [`None`] if the tests do not read the shared example.
+pub fn split_example_tests(module: &str) -> Option<String> {
+    let mut module = module.to_string();
+    let mut changed = false;
+
+    for (part, function) in [(1, "part_one"), (2, "part_two")] {
+        let shared =
+            format!("{function}(&advent_of_code::template::read_file(\"examples\", YEAR, DAY))");
+        if module.contains(&shared) {
+            let split = format!(
+                "{function}(&advent_of_code::template::read_file_part(\"examples\", YEAR, DAY, {part}))"
+            );
+            module = module.replace(&shared, &split);
+            changed = true;
+        }
+    }
+
+    changed.then_some(module)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, split_example_tests, Variables, BUILTIN_TEMPLATE};
+    use crate::{day, template::Year};
+
+    fn variables() -> Variables {
+        Variables {
+            year: Year::new(2024).unwrap(),
+            day: day!(7),
+            title: "Bridge Repair".into(),
+            answer_type: "u64".into(),
+        }
+    }
+
+    #[test]
+    fn renders_variables() {
+        let (module, unknown) = render(
+            "//! %YEAR% day %DAY_PADDED%: %TITLE%\nsolution!(%DAY_NUMBER%);\nfn a() -> Option<%ANSWER_TYPE%> {}\n",
+            &variables(),
+        );
+
+        assert_eq!(
+            module,
+            "//! 2024 day 07: Bridge Repair\nsolution!(7);\nfn a() -> Option<u64> {}\n"
+        );
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn keeps_unknown_variables_and_percent_signs() {
+        let (module, unknown) = render("a % b; %FOO% %FOO% 50%% %YEAR%", &variables());
+        assert_eq!(module, "a % b; %FOO% %FOO% 50%% 2024");
+        assert_eq!(unknown, vec!["FOO".to_string()]);
+    }
+
+    #[test]
+    fn renders_builtin_template() {
+        let (module, unknown) = render(BUILTIN_TEMPLATE, &variables());
+        assert!(module.starts_with("advent_of_code::solution!(7);"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u64> {"));
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn splits_example_tests() {
+        let module = split_example_tests(BUILTIN_TEMPLATE).unwrap();
+        assert!(module.contains(
+            "part_one(&advent_of_code::template::read_file_part(\"examples\", YEAR, DAY, 1));"
+        ));
+        assert!(module.contains(
+            "part_two(&advent_of_code::template::read_file_part(\"examples\", YEAR, DAY, 2));"
+        ));
+        assert!(!module.contains("read_file(\"examples\""));
+
+        assert_eq!(split_example_tests("fn main() {}"), None);
+    }
+}
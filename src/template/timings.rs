@@ -1,17 +1,63 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Error},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    data_dir,
+    stats::{stats_from_json, stats_to_json, BenchStats},
+    Day, Year,
+};
+
+/// Version of the `timings.json` schema written by [`Timings::store_file`].
+/// Files without a version are treated as version 1, which stored durations as formatted strings such as `74.1ms`.
+const SCHEMA_VERSION: u64 = 2;
+
+/// Benchmark time of a single part or of the parse step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Average duration in nanoseconds.
+    pub nanos: u128,
+    /// Number of samples the duration was averaged over, `0` if unknown (e.g. for timings migrated from version 1).
+    pub samples: u128,
+    /// Sample statistics, present if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// Whether the part returned an error. Failed parts do not count towards totals and are benched again.
+    pub failed: bool,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl PartTiming {
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation)]
+        Duration::from_nanos(self.nanos as u64)
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// Time spent in the parse step, for solutions that parse their input separately.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+}
+
+impl Timing {
+    /// Sum of the parse step and both parts in nanoseconds, excluding failed parts.
+    pub fn total_nanos(&self) -> u128 {
+        [self.parse, self.part_1, self.part_2]
+            .iter()
+            .flatten()
+            .filter(|p| !p.failed)
+            .map(|p| p.nanos)
+            .sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,19 +68,31 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of a year, `data/<year>/timings.json`.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let path = get_timings_path(year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    /// Files written by older versions are migrated in memory and upgraded on the next [`Timings::store_file`].
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        Self::read_path(&get_timings_path(year))
+    }
+
+    /// Read timings from a file. Only a missing file yields empty timings, an unreadable or invalid one is an error.
+    pub fn read_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Timings::try_from(contents)
+                .map_err(|e| format!("invalid timings in \"{}\": {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(format!("failed to read \"{}\": {e}", path.display())),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,28 +109,61 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
     /// Sum up total duration of timings as millis.
+    #[allow(clippy::cast_precision_loss)]
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<u128>() as f64 / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day
+                && [t.part_1, t.part_2]
+                    .iter()
+                    .all(|p| p.is_some_and(|p| !p.failed))
+        })
     }
 }
 
+fn get_timings_path(year: Year) -> PathBuf {
+    data_dir(year).join("timings.json")
+}
+
+/// Parse a duration formatted with `{:.1?}`, e.g. `74.1ms` or `379.1µs`, into nanoseconds.
+fn parse_duration_nanos(s: &str) -> Option<u128> {
+    let s = s.trim();
+
+    let (value, unit) = [
+        ("ns", 1.0),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ]
+    .into_iter()
+    .find_map(|(suffix, unit)| s.strip_suffix(suffix).map(|value| (value, unit)))?;
+
+    let value: f64 = value.trim().parse().ok()?;
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((value * unit).round() as u128)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,18 +179,38 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match json.get("version") {
+            None => 1,
+            Some(v) => v
+                .get::<f64>()
+                .filter(|v| v.fract() == 0.0 && **v >= 1.0)
+                .map(|v| *v as u64)
+                .ok_or("expected `json.version` to be a positive integer.")?,
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "unsupported timings version {version}, expecting {SCHEMA_VERSION} or older."
+            ));
+        }
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| match version {
+                    1 => Timing::try_from_v1(timing),
+                    _ => Timing::try_from(timing),
+                })
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -107,31 +218,78 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
-impl From<&Timing> for JsonValue {
-    fn from(value: &Timing) -> Self {
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        }
+        map.insert("stats".into(), stats_to_json(value.stats.as_ref()));
+        map.insert("failed".into(), JsonValue::Boolean(value.failed));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        JsonValue::Object(map)
+    }
+}
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .filter(|v| **v >= 0.0)
+                .map(|v| *v as u128)
+                .ok_or(format!("Expected timing.{key} to be a number."))
+        };
+
+        Ok(PartTiming {
+            nanos: number("nanos")?,
+            samples: number("samples")?,
+            stats: stats_from_json(json, "stats")?,
+            // written since failed parts are recorded, absent in older files.
+            failed: match json.get("failed") {
+                None => false,
+                Some(v) => *v
+                    .get::<bool>()
+                    .ok_or("Expected timing.failed to be a boolean.")?,
             },
-        );
+        })
+    }
+}
+
+fn part_to_json(part: Option<&PartTiming>) -> JsonValue {
+    part.map_or(JsonValue::Null, JsonValue::from)
+}
+
+fn part_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<PartTiming>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => PartTiming::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Timing> for JsonValue {
+    fn from(value: &Timing) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("parse".into(), part_to_json(value.parse.as_ref()));
+        map.insert("part_1".into(), part_to_json(value.part_1.as_ref()));
+        map.insert("part_2".into(), part_to_json(value.part_2.as_ref()));
 
         JsonValue::Object(map)
     }
@@ -145,87 +303,131 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        Ok(Timing {
+            day: day_from_json(json)?,
+            parse: part_from_json(json, "parse")?,
+            part_1: part_from_json(json, "part_1")?,
+            part_2: part_from_json(json, "part_2")?,
+        })
+    }
+}
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+impl Timing {
+    /// Read a timing of schema version 1, where parts were stored as formatted strings with optional `*_stats`.
+    /// The mean of the stats is preferred as it is exact, the string is parsed otherwise.
+    fn try_from_v1(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part = |key: &str, required: bool| -> Result<Option<PartTiming>, String> {
+            let error = || format!("Expected timing.{key} to be null or a duration.");
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+            let formatted = match json.get(key) {
+                None if required => return Err(error()),
+                None => None,
+                Some(v) if v.is_null() => None,
+                Some(v) => Some(v.get::<String>().ok_or_else(error)?),
+            };
+
+            let stats = stats_from_json(json, &format!("{key}_stats"))?;
+
+            let Some(formatted) = formatted else {
+                return Ok(None);
+            };
+
+            Ok(Some(match stats {
+                Some(stats) => PartTiming {
+                    nanos: stats.mean.as_nanos(),
+                    samples: stats.samples as u128,
+                    stats: Some(stats),
+                    failed: false,
+                },
+                None => PartTiming {
+                    nanos: parse_duration_nanos(formatted).ok_or_else(error)?,
+                    samples: 0,
+                    stats: None,
+                    failed: false,
+                },
+            }))
+        };
 
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            day: day_from_json(json)?,
+            parse: part("parse", false)?,
+            part_1: part("part_1", true)?,
+            part_2: part("part_2", true)?,
         })
     }
 }
 
+fn day_from_json(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or_else(|| "Expected timing.day to be a Day struct.".into())
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
-
-    use super::{Timing, Timings};
-
-    fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
-            ],
-        }
+    use super::Timings;
+    use crate::template::fixtures::{get_mock_timings, part};
+    use std::{env, fs, process};
+
+    #[test]
+    fn sums_total_durations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = part(5_000_000);
+        assert_eq!(timings.data[0].total_nanos(), 35_000_000);
+        assert_eq!(timings.total_millis(), 195.0);
     }
 
     mod deserialization {
         use crate::{day, template::timings::Timings};
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.unwrap();
+            assert_eq!(part_1.nanos, 1_000_000);
+            assert_eq!(part_1.samples, 10);
+            assert_eq!(part_1.duration(), Duration::from_millis(1));
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos(), 1_000_000);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 10, "stats": { "samples": 10, "mean_nanos": 1000000, "min_nanos": 900000, "median_nanos": 950000, "p95_nanos": 1500000, "std_dev_nanos": 20000, "outliers": 1 } }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.unwrap().stats.unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.min, Duration::from_nanos(900_000));
+            assert_eq!(stats.outliers, 1);
+        }
+
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000, "samples": 1, "stats": null, "failed": true }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert!(timing.part_1.unwrap().failed);
+
+            let stored = tinyjson::JsonValue::from(timings).stringify().unwrap();
+            assert!(stored.contains(r#""failed":true"#));
         }
 
         #[test]
         fn handles_empty_timings() {
-            let json = r#"{ "data": [] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
         }
@@ -237,6 +439,76 @@ mod tests {
             Timings::try_from(json).unwrap();
         }
 
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_timings() {
+            let json =
+                r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": "1ms" } }] }"#
+                    .to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+    }
+
+    mod migration {
+        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        #[test]
+        fn migrates_string_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.0ns", "part_2": "379.1µs", "total_nanos": 379174 }, { "day": "02", "part_1": "1.3s", "part_2": null, "total_nanos": 1300000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 2);
+
+            let timing = &timings.data[0];
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.part_1.unwrap().nanos, 74);
+            assert_eq!(timing.part_1.unwrap().samples, 0);
+            assert_eq!(timing.part_2.unwrap().nanos, 379_100);
+
+            let timing = &timings.data[1];
+            assert_eq!(timing.part_1.unwrap().nanos, 1_300_000_000);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn prefers_exact_stats() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.0ms", "part_1": "1.0ms", "part_2": null, "total_nanos": 3000000, "part_1_stats": { "samples": 10, "mean_nanos": 1000042, "min_nanos": 900000, "median_nanos": 950000, "p95_nanos": 1500000, "std_dev_nanos": 20000, "outliers": 1 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+
+            let part_1 = timing.part_1.unwrap();
+            assert_eq!(part_1.nanos, 1_000_042);
+            assert_eq!(part_1.samples, 10);
+            assert_eq!(part_1.stats.unwrap().mean, Duration::from_nanos(1_000_042));
+            assert_eq!(timing.parse.unwrap().nanos, 2_000_000);
+        }
+
+        #[test]
+        fn round_trips_migrated_timings() {
+            let json = r#"{ "data": [{ "day": "03", "part_1": "8.8ms", "part_2": "10.0ms", "total_nanos": 18800000 }] }"#.to_string();
+            let migrated = Timings::try_from(json).unwrap();
+            let stored = tinyjson::JsonValue::from(migrated).stringify().unwrap();
+            assert!(stored.contains(r#""version":2"#));
+
+            let timings = Timings::try_from(stored).unwrap();
+            assert_eq!(timings.data[0].part_1.unwrap().nanos, 8_800_000);
+            assert_eq!(timings.data[0].total_nanos(), 18_800_000);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unparsable_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
@@ -246,19 +518,18 @@ mod tests {
     }
 
     mod serialization {
-        use super::get_mock_timings;
+        use crate::template::fixtures::get_mock_timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
+            assert_eq!(json.get("version"), Some(&JsonValue::Number(2.0)));
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
+                json.get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
                     .unwrap()
@@ -271,7 +542,10 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                fixtures::part,
+                timings::{PartTiming, Timing, Timings},
+            },
         };
 
         #[test]
@@ -279,13 +553,13 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    part_1: part(1_000_000),
+                    part_2: part(2_000_000),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -293,13 +567,28 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: part(1_000_000),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
+        }
+
+        #[test]
+        fn handles_failed_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: part(1_000_000),
+                    part_2: part(2_000_000).map(|p| PartTiming { failed: true, ..p }),
+                }],
+            };
+
+            assert!(!timings.is_day_complete(day!(1)));
+            assert_eq!(timings.data[0].total_nanos(), 1_000_000);
         }
 
         #[test]
@@ -307,33 +596,34 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                fixtures::get_mock_timings,
+                timings::{Timing, Timings},
+            },
         };
 
-        use super::get_mock_timings;
-
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +641,9 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,7 +651,7 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
@@ -381,4 +671,22 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    #[test]
+    fn reads_missing_and_invalid_files() {
+        let dir = env::temp_dir().join(format!("aoc-timings-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = Timings::read_path(&dir.join("missing.json"));
+        assert_eq!(missing.map(|t| t.data.len()), Ok(0));
+
+        let invalid = dir.join("invalid.json");
+        fs::write(&invalid, "{\"version\": 99, \"data\": []}").unwrap();
+        assert!(Timings::read_path(&invalid).is_err());
+
+        fs::write(&invalid, "{\"data\": [").unwrap();
+        assert!(Timings::read_path(&invalid).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
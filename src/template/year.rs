@@ -0,0 +1,141 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Name of the environment variable that holds the default year.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// A valid year of advent (i.e. an integer in range 2015 to 9999).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable, usually set in `.cargo/config.toml`.
+    pub fn from_env() -> Option<Self> {
+        env::var(YEAR_ENV).ok()?.parse().ok()
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    // Not part of the public API
+    // Reads the year from the file name of a solution, e.g. `src/bin/2024-01.rs`.
+    #[doc(hidden)]
+    pub const fn __from_bin_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        assert!(
+            bytes.len() - start > 5 && bytes[start + 4] == b'-',
+            "solution files must be named `<year>-<day>.rs`, e.g. `src/bin/2024-01.rs`"
+        );
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < 4 {
+            let digit = bytes[start + i];
+            assert!(
+                digit.is_ascii_digit(),
+                "solution files must be named `<year>-<day>.rs`, e.g. `src/bin/2024-01.rs`"
+            );
+            year = year * 10 + (digit - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= 2015, "invalid year, expecting 2015 or later");
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.trim().parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().ok(), Year::new(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_bin_path() {
+        assert_eq!(Year::__from_bin_path("src/bin/2023-07.rs"), 2023);
+        assert_eq!(Year::__from_bin_path("/abs/path/src/bin/2024-01.rs"), 2024);
+        assert_eq!(Year::__from_bin_path("C:\\aoc\\src\\bin\\2016-25.rs"), 2016);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_bin_paths_without_year() {
+        Year::__from_bin_path("src/bin/01.rs");
+    }
+}